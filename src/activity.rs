@@ -0,0 +1,147 @@
+//! Events reporting how cameras move.
+
+use crate::{OrbitCameraPlugin, OrbitCameraState};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ActivityKind {
+    /// The camera started moving.
+    InteractionStarted,
+    /// Change in `x` and `y`, in radians.
+    Orbited(Vec2),
+    /// Change in `center`.
+    Panned(Vec3),
+    /// Ratio of the new `distance` to the old one.
+    Zoomed(f32),
+    /// The camera came to rest.
+    InteractionEnded,
+}
+
+/// Sent for every frame a camera moves, once smoothing has been applied, for
+/// updating overlays or a minimap only while the view changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraActivity {
+    pub camera: Entity,
+    pub kind: ActivityKind,
+}
+
+/// Sent once a camera has been still for `idle_gap` seconds after moving,
+/// summing up the burst of interaction, for logging engagement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InteractionSummary {
+    pub camera: Entity,
+    /// Seconds from the first frame the camera moved to the last.
+    pub duration: f32,
+    /// Radians turned, in any direction.
+    pub total_rotation: f32,
+    /// World units the center moved, in any direction.
+    pub total_pan: f32,
+    /// Ratio of the distance at the end to the one at the start.
+    pub total_zoom: f32,
+}
+
+/// When an [`InteractionSummary`] is sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InteractionSummarySettings {
+    /// Seconds a camera stays still before its burst of interaction counts
+    /// as over. Shorter pauses are part of the same burst.
+    pub idle_gap: f32,
+}
+
+impl Default for InteractionSummarySettings {
+    fn default() -> Self {
+        InteractionSummarySettings { idle_gap: 1.0 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Burst {
+    start: f64,
+    last_active: f64,
+    rotation: f32,
+    pan: f32,
+    zoom: f32,
+}
+
+/// The bursts of interaction in progress.
+#[derive(Default)]
+pub struct InteractionTracker {
+    bursts: HashMap<Entity, Burst>,
+}
+
+impl OrbitCameraPlugin {
+    pub fn camera_activity_system(
+        mut moving: Local<HashSet<Entity>>,
+        mut events: EventWriter<CameraActivity>,
+        query: Query<(Entity, &OrbitCameraState), Changed<OrbitCameraState>>,
+    ) {
+        for (entity, state) in query.iter() {
+            let mut send = |kind| {
+                events.send(CameraActivity {
+                    camera: entity,
+                    kind,
+                })
+            };
+            if !state.is_moving() {
+                if moving.remove(&entity) {
+                    send(ActivityKind::InteractionEnded);
+                }
+                continue;
+            }
+            if moving.insert(entity) {
+                send(ActivityKind::InteractionStarted);
+            }
+            if state.last_rotation_delta != Vec2::ZERO {
+                send(ActivityKind::Orbited(state.last_rotation_delta));
+            }
+            if state.last_pan_delta != Vec3::ZERO {
+                send(ActivityKind::Panned(state.last_pan_delta));
+            }
+            if state.last_zoom_factor != 1.0 {
+                send(ActivityKind::Zoomed(state.last_zoom_factor));
+            }
+        }
+    }
+
+    pub fn interaction_summary_system(
+        mut tracker: Local<InteractionTracker>,
+        mut events: EventWriter<InteractionSummary>,
+        settings: Res<InteractionSummarySettings>,
+        time: Res<Time>,
+        query: Query<(Entity, &OrbitCameraState)>,
+    ) {
+        let now = time.seconds_since_startup();
+        for (entity, state) in query.iter() {
+            if state.is_moving() {
+                let burst = tracker.bursts.entry(entity).or_insert(Burst {
+                    start: now,
+                    last_active: now,
+                    rotation: 0.0,
+                    pan: 0.0,
+                    zoom: 1.0,
+                });
+                burst.last_active = now;
+                burst.rotation += state.last_rotation_delta.length();
+                burst.pan += state.last_pan_delta.length();
+                burst.zoom *= state.last_zoom_factor;
+                continue;
+            }
+            let over = tracker.bursts.get(&entity).map_or(false, |burst| {
+                now - burst.last_active >= settings.idle_gap as f64
+            });
+            if !over {
+                continue;
+            }
+            if let Some(burst) = tracker.bursts.remove(&entity) {
+                events.send(InteractionSummary {
+                    camera: entity,
+                    duration: (burst.last_active - burst.start) as f32,
+                    total_rotation: burst.rotation,
+                    total_pan: burst.pan,
+                    total_zoom: burst.zoom,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Mouse buttons chorded with modifier keys.
+
+use crate::OrbitCamera;
+use bevy::prelude::*;
+
+/// Modifier keys, on either side of the keyboard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers {
+        shift: false,
+        ctrl: false,
+        alt: false,
+    };
+    pub const SHIFT: Modifiers = Modifiers {
+        shift: true,
+        ..Modifiers::NONE
+    };
+    pub const CTRL: Modifiers = Modifiers {
+        ctrl: true,
+        ..Modifiers::NONE
+    };
+    pub const ALT: Modifiers = Modifiers {
+        alt: true,
+        ..Modifiers::NONE
+    };
+
+    /// The modifiers held down.
+    pub fn held(keyboard_input: &Input<KeyCode>) -> Self {
+        let any = |keys: [KeyCode; 2]| keys.iter().any(|key| keyboard_input.pressed(*key));
+        Modifiers {
+            shift: any([KeyCode::LShift, KeyCode::RShift]),
+            ctrl: any([KeyCode::LControl, KeyCode::RControl]),
+            alt: any([KeyCode::LAlt, KeyCode::RAlt]),
+        }
+    }
+
+    /// Whether every modifier of `other` is among these.
+    pub fn contains(&self, other: Modifiers) -> bool {
+        (self.shift || !other.shift) && (self.ctrl || !other.ctrl) && (self.alt || !other.alt)
+    }
+
+    fn count(&self) -> usize {
+        [self.shift, self.ctrl, self.alt]
+            .iter()
+            .filter(|held| **held)
+            .count()
+    }
+}
+
+/// A mouse button held together with modifier keys, such as Alt and the left
+/// button to orbit like in Maya. Extra modifiers don't stop a binding, unless
+/// another binding of the same button needs them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseBinding {
+    pub button: MouseButton,
+    pub modifiers: Modifiers,
+}
+
+impl MouseBinding {
+    pub fn new(button: MouseButton, modifiers: Modifiers) -> Self {
+        MouseBinding { button, modifiers }
+    }
+
+    fn matches(&self, mouse_button_input: &Input<MouseButton>, held: Modifiers) -> bool {
+        mouse_button_input.pressed(self.button) && held.contains(self.modifiers)
+    }
+}
+
+impl From<MouseButton> for MouseBinding {
+    fn from(button: MouseButton) -> Self {
+        MouseBinding::new(button, Modifiers::NONE)
+    }
+}
+
+/// Which of a camera's drag bindings are held.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Drags {
+    pub rotate: bool,
+    pub pan: bool,
+    pub zoom: bool,
+}
+
+impl OrbitCamera {
+    /// Holding a button with modifiers only drives the binding of that button
+    /// that needs the most of them.
+    pub(crate) fn drags(
+        &self,
+        mouse_button_input: &Input<MouseButton>,
+        keyboard_input: &Input<KeyCode>,
+    ) -> Drags {
+        let held = Modifiers::held(keyboard_input);
+        let bindings = [
+            Some(self.rotate_binding),
+            Some(self.pan_binding),
+            self.zoom_binding,
+        ];
+        let matching: Vec<Option<MouseBinding>> = bindings
+            .iter()
+            .map(|binding| binding.filter(|binding| binding.matches(mouse_button_input, held)))
+            .collect();
+        let active = |index: usize| {
+            matching[index].map_or(false, |binding| {
+                !matching.iter().flatten().any(|other| {
+                    other.button == binding.button
+                        && other.modifiers.count() > binding.modifiers.count()
+                })
+            })
+        };
+        Drags {
+            rotate: active(0),
+            pan: active(1),
+            zoom: active(2),
+        }
+    }
+
+    /// The mouse buttons of the camera's drag bindings.
+    pub(crate) fn drag_buttons(&self) -> Vec<MouseButton> {
+        let mut buttons = vec![self.rotate_binding.button, self.pan_binding.button];
+        buttons.extend(self.zoom_binding.map(|binding| binding.button));
+        buttons
+    }
+}
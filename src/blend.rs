@@ -0,0 +1,181 @@
+//! Mixing the poses that different behaviors want.
+
+use crate::trackball::wrap_angle;
+use crate::{OrbitCameraPlugin, OrbitCameraState, OrbitPose};
+use bevy::prelude::*;
+
+/// A pose one behavior wants the camera in, such as a tour, a follow shot or
+/// a zone's view, mixed in by a [`CameraBlendStack`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlendLayer {
+    pub pose: OrbitPose,
+    /// Layers with higher priorities cover those with lower ones.
+    pub priority: i32,
+    /// How much of the layer shows once faded in, from zero to one.
+    pub weight: f32,
+    /// Seconds taken to fade in after being added.
+    pub fade_in: f32,
+    /// Seconds taken to fade out after being released.
+    pub fade_out: f32,
+    released: bool,
+    fade: f32,
+}
+
+impl BlendLayer {
+    pub fn new(pose: OrbitPose, priority: i32) -> Self {
+        BlendLayer {
+            pose,
+            priority,
+            weight: 1.0,
+            fade_in: 0.5,
+            fade_out: 0.5,
+            released: false,
+            fade: 0.0,
+        }
+    }
+
+    /// How much of the layer shows right now.
+    pub fn current_weight(&self) -> f32 {
+        let t = self.fade;
+        self.weight.clamp(0.0, 1.0) * t * t * (3.0 - 2.0 * t)
+    }
+
+    pub fn is_released(&self) -> bool {
+        self.released
+    }
+
+    fn is_fading(&self) -> bool {
+        self.fade != if self.released { 0.0 } else { 1.0 }
+    }
+
+    /// Moves the fade on by `dt` seconds.
+    fn advance(&mut self, dt: f32) {
+        let (goal, duration) = if self.released {
+            (0.0, self.fade_out)
+        } else {
+            (1.0, self.fade_in)
+        };
+        self.fade = if duration > 0.0 {
+            let step = dt / duration;
+            if goal > self.fade {
+                (self.fade + step).min(goal)
+            } else {
+                (self.fade - step).max(goal)
+            }
+        } else {
+            goal
+        };
+    }
+}
+
+/// Mixes [`BlendLayer`]s over the pose of the camera on the same entity, so
+/// behaviors that want the camera in different places compose instead of
+/// overwriting each other. The camera's own pose stays under the user's
+/// control beneath the layers, and shows again as they fade out. Layers are
+/// mixed from the lowest priority up, each covering those below by its
+/// weight.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraBlendStack {
+    layers: Vec<(String, BlendLayer)>,
+}
+
+impl CameraBlendStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer, fading it in, or replaces the one called `name` and
+    /// carries on from its fade.
+    pub fn insert(&mut self, name: impl Into<String>, mut layer: BlendLayer) {
+        let name = name.into();
+        match self.layers.iter_mut().find(|(other, _)| *other == name) {
+            Some((_, existing)) => {
+                layer.fade = existing.fade;
+                *existing = layer;
+            }
+            None => self.layers.push((name, layer)),
+        }
+    }
+
+    /// Updates the pose a layer wants, such as every frame as its behavior
+    /// moves.
+    pub fn set_pose(&mut self, name: &str, pose: OrbitPose) {
+        if let Some(layer) = self.layer_mut(name) {
+            layer.pose = pose;
+        }
+    }
+
+    /// Fades a layer out, removing it once it's gone.
+    pub fn release(&mut self, name: &str) {
+        if let Some(layer) = self.layer_mut(name) {
+            layer.released = true;
+        }
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&BlendLayer> {
+        self.layers
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, layer)| layer)
+    }
+
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut BlendLayer> {
+        self.layers
+            .iter_mut()
+            .find(|(other, _)| other == name)
+            .map(|(_, layer)| layer)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Moves the layers' poses along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        for (_, layer) in self.layers.iter_mut() {
+            layer.pose.center += offset;
+        }
+    }
+
+    /// `state` with the layers mixed over it.
+    pub fn blend(&self, state: &OrbitCameraState) -> OrbitCameraState {
+        let mut layers: Vec<&BlendLayer> = self.layers.iter().map(|(_, layer)| layer).collect();
+        layers.sort_by_key(|layer| layer.priority);
+        let mut blended = *state;
+        for layer in layers {
+            let t = layer.current_weight();
+            if t <= 0.0 {
+                continue;
+            }
+            let pose = layer.pose;
+            blended.x += wrap_angle(pose.x - blended.x) * t;
+            blended.y += (pose.y - blended.y) * t;
+            blended.distance = if blended.distance > 0.0 && pose.distance > 0.0 {
+                blended.distance * (pose.distance / blended.distance).powf(t)
+            } else {
+                blended.distance + (pose.distance - blended.distance) * t
+            };
+            blended.center = blended.center.lerp(pose.center, t);
+        }
+        blended
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn camera_blend_stack_system(time: Res<Time>, mut query: Query<&mut CameraBlendStack>) {
+        let dt = time.delta_seconds();
+        let finished = |layer: &BlendLayer| layer.released && layer.fade == 0.0;
+        for mut stack in query.iter_mut() {
+            if stack.layers.iter().any(|(_, layer)| layer.is_fading()) {
+                for (_, layer) in stack.layers.iter_mut() {
+                    layer.advance(dt);
+                }
+            }
+            // Layers released while already faded out never fade, so they are
+            // checked on their own.
+            if stack.layers.iter().any(|(_, layer)| finished(layer)) {
+                stack.layers.retain(|(_, layer)| !finished(layer));
+            }
+        }
+    }
+}
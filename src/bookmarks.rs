@@ -0,0 +1,65 @@
+//! Saved views.
+
+use crate::{OrbitCamera, OrbitPose};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bookmark {
+    pub name: String,
+    pub pose: OrbitPose,
+}
+
+/// Named views saved for the camera on the same entity, in the order they
+/// were first saved.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraBookmarks {
+    bookmarks: Vec<Bookmark>,
+}
+
+impl CameraBookmarks {
+    /// Saves the current view, replacing any bookmark with the same name but
+    /// keeping its place.
+    pub fn save(&mut self, name: impl Into<String>, camera: &OrbitCamera) -> &mut Bookmark {
+        let name = name.into();
+        let pose = OrbitPose::from(camera);
+        let index = match self.bookmarks.iter().position(|b| b.name == name) {
+            Some(index) => {
+                self.bookmarks[index].pose = pose;
+                index
+            }
+            None => {
+                self.bookmarks.push(Bookmark { name, pose });
+                self.bookmarks.len() - 1
+            }
+        };
+        &mut self.bookmarks[index]
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Bookmark> {
+        self.bookmarks.iter().find(|b| b.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Bookmark> {
+        self.bookmarks.iter_mut().find(|b| b.name == name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Bookmark> {
+        let index = self.bookmarks.iter().position(|b| b.name == name)?;
+        Some(self.bookmarks.remove(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Bookmark> {
+        self.bookmarks.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bookmarks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bookmarks.clear();
+    }
+}
@@ -0,0 +1,247 @@
+//! Keeping an object inside the view.
+
+use crate::{CameraFeedback, CameraLimit, CameraMode, FrameBudget, OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+
+/// An axis-aligned box in the local space of the entity it's on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitBounds {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl OrbitBounds {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        OrbitBounds { min, max }
+    }
+
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        OrbitBounds {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    pub fn from_point(point: Vec3) -> Self {
+        OrbitBounds::new(point, point)
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) / 2.0
+    }
+
+    /// The eight corners of the box.
+    pub fn corners(&self) -> [Vec3; 8] {
+        let mut corners = [Vec3::ZERO; 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            *corner = Vec3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            );
+        }
+        corners
+    }
+
+    /// The center and radius of a sphere enclosing the box once placed by
+    /// `transform`.
+    pub fn bounding_sphere(&self, transform: &GlobalTransform) -> (Vec3, f32) {
+        let center = transform.mul_vec3(self.center());
+        let radius = self
+            .corners()
+            .iter()
+            .map(|corner| transform.mul_vec3(*corner).distance(center))
+            .fold(0.0, f32::max);
+        (center, radius)
+    }
+
+    /// The axis-aligned box enclosing this one once placed by `transform`.
+    pub fn transformed(&self, transform: &GlobalTransform) -> Self {
+        let start = OrbitBounds::from_point(transform.mul_vec3(self.min));
+        self.corners().iter().fold(start, |bounds, corner| {
+            bounds.union(&OrbitBounds::from_point(transform.mul_vec3(*corner)))
+        })
+    }
+
+    /// The box enclosing both this one and `other`.
+    pub fn union(&self, other: &OrbitBounds) -> Self {
+        OrbitBounds::new(self.min.min(other.min), self.max.max(other.max))
+    }
+}
+
+/// The distance from `center` at which a sphere never leaves a perspective
+/// view with vertical field of view `fov` and `aspect_ratio`.
+pub fn framing_distance(sphere: (Vec3, f32), center: Vec3, fov: f32, aspect_ratio: f32) -> f32 {
+    let (sphere_center, radius) = sphere;
+    let half_vertical = fov / 2.0;
+    let half_horizontal = (half_vertical.tan() * aspect_ratio).atan();
+    let half_angle = half_vertical.min(half_horizontal);
+    if half_angle <= 0.0 {
+        return 0.0;
+    }
+    // The sphere may be off the orbit center, so allow for the worst case.
+    radius / half_angle.sin() + sphere_center.distance(center)
+}
+
+/// Keeps a perspective camera far enough from its center that the
+/// [`OrbitBounds`] of `target` always fit in the frustum, whatever the field
+/// of view and aspect ratio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrustumDistanceLimit {
+    pub target: Entity,
+    /// Extra room around the bounds, as a fraction of their radius.
+    pub padding: f32,
+    distance: f32,
+}
+
+impl FrustumDistanceLimit {
+    pub fn new(target: Entity) -> Self {
+        FrustumDistanceLimit {
+            target,
+            padding: 0.0,
+            distance: 0.0,
+        }
+    }
+
+    /// The current limit, refitted as often as the [`FrameBudget`] allows.
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn frustum_distance_limit_system(
+        mut feedback: EventWriter<CameraFeedback>,
+        budget: Res<FrameBudget>,
+        targets: Query<(&OrbitBounds, &GlobalTransform)>,
+        mut query: Query<(
+            Entity,
+            &mut OrbitCamera,
+            &PerspectiveProjection,
+            &mut FrustumDistanceLimit,
+        )>,
+    ) {
+        for (entity, mut camera, projection, mut limit) in query.iter_mut() {
+            let fitted = limit.distance > 0.0;
+            if !fitted || budget.is_due_for(budget.frustum_limit_interval, entity) {
+                let (bounds, transform) = match targets.get(limit.target) {
+                    Ok(target) => target,
+                    Err(_) => continue,
+                };
+                let (sphere_center, radius) = bounds.bounding_sphere(transform);
+                let fov = camera.fov.unwrap_or(projection.fov);
+                limit.distance = framing_distance(
+                    (sphere_center, radius * (1.0 + limit.padding)),
+                    camera.center,
+                    fov,
+                    projection.aspect_ratio,
+                );
+            }
+            if camera.mode == CameraMode::Orbit && camera.distance < limit.distance {
+                camera.distance = limit.distance;
+                feedback.send(CameraFeedback::limit(entity, CameraLimit::Distance));
+            }
+        }
+    }
+}
+
+/// Marks entities whose [`OrbitBounds`] make up the [`SceneBounds`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SceneBoundsMember;
+
+/// The combined world-space bounds of every [`SceneBoundsMember`], kept up to
+/// date as they move, change or are despawned, as often as the
+/// [`FrameBudget`] allows.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SceneBounds {
+    bounds: Option<OrbitBounds>,
+}
+
+impl SceneBounds {
+    pub fn get(&self) -> Option<OrbitBounds> {
+        self.bounds
+    }
+}
+
+/// Keeps the camera on the same entity from being panned outside the
+/// [`SceneBounds`], widened by `margin`, or zoomed out further than needed to
+/// see all of them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SceneBoundary {
+    pub margin: f32,
+}
+
+impl Default for SceneBoundary {
+    fn default() -> Self {
+        SceneBoundary { margin: 1.0 }
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn scene_bounds_system(
+        mut scene: ResMut<SceneBounds>,
+        budget: Res<FrameBudget>,
+        members: Query<(&OrbitBounds, &GlobalTransform), With<SceneBoundsMember>>,
+    ) {
+        if !budget.is_due(budget.scene_bounds_interval) {
+            return;
+        }
+        let mut combined: Option<OrbitBounds> = None;
+        for (bounds, transform) in members.iter() {
+            for corner in bounds.corners().iter() {
+                let point = transform.mul_vec3(*corner);
+                combined = Some(match combined {
+                    Some(combined) => {
+                        OrbitBounds::new(combined.min.min(point), combined.max.max(point))
+                    }
+                    None => OrbitBounds::new(point, point),
+                });
+            }
+        }
+        if scene.bounds != combined {
+            scene.bounds = combined;
+        }
+    }
+
+    pub fn scene_boundary_system(
+        mut feedback: EventWriter<CameraFeedback>,
+        scene: Res<SceneBounds>,
+        mut query: Query<(
+            Entity,
+            &mut OrbitCamera,
+            Option<&PerspectiveProjection>,
+            &SceneBoundary,
+        )>,
+    ) {
+        let bounds = match scene.bounds {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        for (entity, mut camera, projection, boundary) in query.iter_mut() {
+            let margin = Vec3::splat(boundary.margin.max(0.0));
+            let center = camera
+                .center
+                .max(bounds.min - margin)
+                .min(bounds.max + margin);
+            if center != camera.center {
+                camera.center = center;
+                feedback.send(CameraFeedback::limit(entity, CameraLimit::Bounds));
+            }
+            if let (CameraMode::Orbit, Some(projection)) = (camera.mode, projection) {
+                let sphere = (bounds.center(), bounds.half_extents().length());
+                let fov = camera.fov.unwrap_or(projection.fov);
+                let max = framing_distance(sphere, center, fov, projection.aspect_ratio)
+                    + boundary.margin.max(0.0);
+                if camera.distance > max {
+                    camera.distance = max;
+                    feedback.send(CameraFeedback::limit(entity, CameraLimit::Bounds));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Throttling the plugin's heavier work.
+
+use crate::OrbitCameraPlugin;
+use bevy::prelude::*;
+
+/// How often the plugin redoes its heavier work, to keep it cheap on low-end
+/// devices. Intervals are in frames, so one runs every frame; results are
+/// kept in between.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameBudget {
+    /// Recombining the [`SceneBounds`](crate::SceneBounds).
+    pub scene_bounds_interval: u32,
+    /// Refitting each [`FrustumDistanceLimit`](crate::FrustumDistanceLimit).
+    /// Cameras are spread over the interval rather than refitted together.
+    pub frustum_limit_interval: u32,
+    /// The most rays a [`CameraCollision`](crate::CameraCollision) casts for
+    /// one move, each letting it slide along one more surface.
+    pub collision_casts: usize,
+    frame: u32,
+}
+
+impl FrameBudget {
+    /// The number of frames counted so far.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Whether work done every `interval` frames is due this frame.
+    pub fn is_due(&self, interval: u32) -> bool {
+        interval <= 1 || self.frame % interval == 0
+    }
+
+    /// Like [`FrameBudget::is_due`], but staggered by `entity` so work for
+    /// many entities is spread over the interval.
+    pub fn is_due_for(&self, interval: u32, entity: Entity) -> bool {
+        interval <= 1 || self.frame.wrapping_add(entity.id()) % interval == 0
+    }
+}
+
+impl Default for FrameBudget {
+    fn default() -> Self {
+        FrameBudget {
+            scene_bounds_interval: 1,
+            frustum_limit_interval: 1,
+            collision_casts: 3,
+            frame: 0,
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn frame_budget_system(mut budget: ResMut<FrameBudget>) {
+        budget.frame = budget.frame.wrapping_add(1);
+    }
+}
@@ -0,0 +1,91 @@
+//! Driving cameras from game code through events.
+
+use crate::pivot::{orbit_rotation, up_rotation};
+use crate::trackball::wrap_angle;
+use crate::{CameraMode, OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraCommandKind {
+    SetCenter(Vec3),
+    SetDistance(f32),
+    /// Sets `x` and `y`, in radians.
+    SetAngles {
+        x: f32,
+        y: f32,
+    },
+    /// Turns the camera towards a point without moving it. Orbiting cameras
+    /// take the point as their new center.
+    LookAt(Vec3),
+}
+
+/// Sets the target of `camera` from game code. The camera moves there with
+/// its usual smoothing and limits, like after any other input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitCameraCommand {
+    pub camera: Entity,
+    pub kind: CameraCommandKind,
+}
+
+impl CameraCommandKind {
+    pub fn apply(&self, camera: &mut OrbitCamera) {
+        match *self {
+            CameraCommandKind::SetCenter(center) => camera.center = center,
+            CameraCommandKind::SetDistance(distance) => camera.distance = distance,
+            CameraCommandKind::SetAngles { x, y } => {
+                camera.x = x;
+                camera.y = y;
+            }
+            CameraCommandKind::LookAt(point) => look_at(camera, point),
+        }
+    }
+}
+
+fn look_at(camera: &mut OrbitCamera, point: Vec3) {
+    let offset = up_rotation(camera.up) * (orbit_rotation(camera.x, camera.y) * Vec3::Y);
+    let back = match camera.mode {
+        CameraMode::Orbit => {
+            let eye = camera.center + offset * camera.distance;
+            let distance = (eye - point).length();
+            if distance < f32::EPSILON {
+                return;
+            }
+            camera.center = point;
+            camera.distance = distance;
+            (eye - point) / distance
+        }
+        CameraMode::LookAround | CameraMode::Walk => {
+            let back = camera.center - point;
+            if back.length() < f32::EPSILON {
+                return;
+            }
+            back.normalize()
+        }
+        CameraMode::Map => {
+            camera.center = point;
+            return;
+        }
+    };
+    set_direction(camera, back);
+}
+
+/// Sets `x` and `y` so that the eye lies along the unit vector `back` from
+/// the center, turning `x` the short way round.
+pub(crate) fn set_direction(camera: &mut OrbitCamera, back: Vec3) {
+    let back = up_rotation(camera.up).inverse() * back;
+    camera.x += wrap_angle((-back.x).atan2(-back.z) - camera.x);
+    camera.y = back.y.clamp(-1.0, 1.0).acos();
+}
+
+impl OrbitCameraPlugin {
+    pub fn camera_command_system(
+        mut events: EventReader<OrbitCameraCommand>,
+        mut query: Query<&mut OrbitCamera>,
+    ) {
+        for event in events.iter() {
+            if let Ok(mut camera) = query.get_mut(event.camera) {
+                event.kind.apply(&mut camera);
+            }
+        }
+    }
+}
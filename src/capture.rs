@@ -0,0 +1,61 @@
+//! UI that has taken the input from the cameras.
+
+use crate::OrbitCameraPlugin;
+use bevy::prelude::*;
+
+/// Whether UI has taken the pointer or the keyboard, such as an egui window
+/// under the cursor or a focused text field. While it has, cameras ignore the
+/// mouse and touch, or the keyboard. Set it every frame before the
+/// [`OrbitCameraSystem::Input`](crate::OrbitCameraSystem::Input) systems run,
+/// such as from `wants_pointer_input` and `wants_keyboard_input` of bevy_egui.
+///
+/// This is the app's own flag, which the plugin only reads. Separately, the
+/// plugin keeps a [`UiPointerCapture`] of whether the cursor is over a Bevy UI
+/// node with an `Interaction`, and cameras ignore the pointer while either
+/// says so.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InputCapture {
+    pub pointer: bool,
+    pub keyboard: bool,
+}
+
+impl InputCapture {
+    /// Whether the app or Bevy UI has taken the pointer.
+    pub(crate) fn pointer_taken(&self, ui: &UiPointerCapture) -> bool {
+        self.pointer || ui.over_ui
+    }
+}
+
+/// Whether the pointer was over Bevy UI when the current press started, kept
+/// by the plugin apart from the app's [`InputCapture`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UiPointerCapture {
+    over_ui: bool,
+}
+
+impl UiPointerCapture {
+    pub fn is_over_ui(&self) -> bool {
+        self.over_ui
+    }
+}
+
+impl OrbitCameraPlugin {
+    /// Takes the pointer while it's over a Bevy UI node with an
+    /// `Interaction`, so clicking a button doesn't also rotate the scene.
+    /// Whether it's over the UI is only checked while no button is held or as
+    /// one is pressed, so drags that start in the scene carry on over the UI
+    /// and drags that start on a slider don't reach the cameras.
+    pub fn ui_capture_system(
+        mut ui: ResMut<UiPointerCapture>,
+        mouse_button_input: Res<Input<MouseButton>>,
+        interactions: Query<&Interaction>,
+    ) {
+        let held = mouse_button_input.get_pressed().next().is_some();
+        let pressed = mouse_button_input.get_just_pressed().next().is_some();
+        if !held || pressed {
+            ui.over_ui = interactions
+                .iter()
+                .any(|interaction| *interaction != Interaction::None);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Keeping the camera out of solid geometry.
+
+use crate::pivot::{orbit_rotation, up_rotation};
+use crate::{
+    CameraMode, CameraSuspended, OrbitCamera, OrbitCameraPlugin, OrbitCameraState, PitchDistance,
+    Ray, Raycaster,
+};
+use bevy::prelude::*;
+
+/// Keeps the camera on the same entity out of the geometry found by
+/// [`RaycastProvider::cast`](crate::RaycastProvider::cast) on the
+/// [`Raycaster`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraCollision {
+    /// How close, in world units, the orbit center and the eye may come to a
+    /// surface.
+    pub radius: f32,
+    /// Whether panning slides the orbit center along surfaces instead of
+    /// passing through them.
+    pub pan: bool,
+    /// Whether an orbiting camera is pulled in towards the center when
+    /// geometry comes between them, like a spring arm, without changing its
+    /// `distance`.
+    pub spring_arm: bool,
+    /// The closest the spring arm pulls the eye to the center.
+    pub min_distance: f32,
+    /// Seconds the spring arm takes to ease most of the way back out once
+    /// clear.
+    pub restore_time: f32,
+    arm: Option<f32>,
+}
+
+impl CameraCollision {
+    pub fn new() -> Self {
+        CameraCollision {
+            radius: 0.2,
+            pan: true,
+            spring_arm: true,
+            min_distance: 0.1,
+            restore_time: 0.3,
+            arm: None,
+        }
+    }
+
+    /// The distance the eye is pulled in to while geometry is in the way.
+    pub fn arm(&self) -> Option<f32> {
+        self.arm
+    }
+
+    /// Where a move from `from` by `delta` ends, sliding along the surfaces
+    /// it meets with up to `casts` rays, and whether it met any.
+    pub fn slide(
+        &self,
+        raycaster: &Raycaster,
+        from: Vec3,
+        delta: Vec3,
+        casts: usize,
+    ) -> (Vec3, bool) {
+        let mut position = from;
+        let mut remaining = delta;
+        let mut hit = false;
+        for _ in 0..casts {
+            let length = remaining.length();
+            if length < f32::EPSILON {
+                break;
+            }
+            let direction = remaining / length;
+            let ray = Ray {
+                origin: position,
+                direction,
+            };
+            match raycaster.cast(&ray, length + self.radius) {
+                Some(surface) => {
+                    hit = true;
+                    let travel = (surface.distance - self.radius).max(0.0).min(length);
+                    position += direction * travel;
+                    let left = remaining - direction * travel;
+                    remaining = left - surface.normal * left.dot(surface.normal);
+                }
+                None => {
+                    position += remaining;
+                    break;
+                }
+            }
+        }
+        (position, hit)
+    }
+}
+
+impl Default for CameraCollision {
+    fn default() -> Self {
+        CameraCollision::new()
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn spring_arm_system(
+        time: Res<Time>,
+        raycaster: Res<Raycaster>,
+        mut query: Query<
+            (
+                &OrbitCamera,
+                &OrbitCameraState,
+                &mut CameraCollision,
+                Option<&PitchDistance>,
+            ),
+            Without<CameraSuspended>,
+        >,
+    ) {
+        for (camera, state, mut collision, crane) in query.iter_mut() {
+            if !collision.spring_arm || camera.mode != CameraMode::Orbit || !camera.enabled {
+                if collision.arm.is_some() {
+                    collision.arm = None;
+                }
+                continue;
+            }
+            let distance = crane.map_or(state.distance, |crane| {
+                state.distance * crane.scale(state.y)
+            });
+            let direction = up_rotation(state.up) * (orbit_rotation(state.x, state.y) * Vec3::Y);
+            let ray = Ray {
+                origin: state.center,
+                direction,
+            };
+            let reach = match raycaster.cast(&ray, distance + collision.radius) {
+                Some(hit) => (hit.distance - collision.radius).max(collision.min_distance),
+                None => distance,
+            };
+            let current = collision.arm.unwrap_or(distance);
+            // Pulled in at once, so the view is never blocked, and let out gently.
+            let arm = if reach < current || collision.restore_time <= 0.0 {
+                reach
+            } else {
+                let t = 1.0 - (-time.delta_seconds() / collision.restore_time).exp();
+                current + (reach - current) * t
+            };
+            // Let go once it's nearly all the way out.
+            let arm = Some(arm).filter(|arm| *arm < distance * 0.999);
+            if collision.arm != arm {
+                collision.arm = arm;
+            }
+        }
+    }
+}
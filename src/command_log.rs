@@ -0,0 +1,193 @@
+//! Logging every change to a camera's target as replayable commands.
+
+use crate::{OrbitCamera, OrbitCameraPlugin, OrbitPose};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// One change to the target of an [`OrbitCamera`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OrbitCommand {
+    /// Adds to `x` and `y`, in radians.
+    Rotate(Vec2),
+    /// Moves `center`.
+    Pan(Vec3),
+    /// Multiplies `distance`.
+    Zoom(f32),
+    SetPose(OrbitPose),
+}
+
+impl OrbitCommand {
+    pub fn apply(&self, camera: &mut OrbitCamera) {
+        match *self {
+            OrbitCommand::Rotate(delta) => {
+                camera.x += delta.x;
+                camera.y += delta.y;
+            }
+            OrbitCommand::Pan(delta) => camera.center += delta,
+            OrbitCommand::Zoom(factor) => camera.distance *= factor,
+            OrbitCommand::SetPose(pose) => pose.apply(camera),
+        }
+    }
+}
+
+/// An [`OrbitCommand`] as it was logged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoggedCommand {
+    /// Seconds since startup.
+    pub time: f64,
+    pub command: OrbitCommand,
+    /// The pose before the command, for undoing it.
+    pub before: OrbitPose,
+}
+
+/// Logs every change to the target of the camera on the same entity, whether
+/// made through [`CommandLog::push`] or by other systems, so replay, undo and
+/// network sync can all be built on the same entries. Changes made by other
+/// systems are logged as the rotation, pan and zoom they amount to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommandLog {
+    /// The most entries kept; older ones are dropped.
+    pub capacity: usize,
+    entries: VecDeque<LoggedCommand>,
+    pending: Vec<OrbitCommand>,
+    replay: VecDeque<LoggedCommand>,
+    replay_offset: Option<f64>,
+    last: Option<OrbitPose>,
+}
+
+impl CommandLog {
+    pub fn new() -> Self {
+        CommandLog {
+            capacity: 1000,
+            entries: VecDeque::new(),
+            pending: Vec::new(),
+            replay: VecDeque::new(),
+            replay_offset: None,
+            last: None,
+        }
+    }
+
+    /// Applies `command` to the camera on the next update.
+    pub fn push(&mut self, command: OrbitCommand) {
+        self.pending.push(command);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &LoggedCommand> {
+        self.entries.iter()
+    }
+
+    /// Removes and returns the logged entries, such as to send them to peers.
+    pub fn take_entries(&mut self) -> Vec<LoggedCommand> {
+        self.entries.drain(..).collect()
+    }
+
+    /// Restores the pose from before the last entry, removing it without
+    /// logging the restore.
+    pub fn undo(&mut self) -> Option<LoggedCommand> {
+        let entry = self.entries.pop_back()?;
+        // Entries logged in the same frame are undone together.
+        let mut before = entry.before;
+        while let Some(previous) = self.entries.back() {
+            if previous.time != entry.time {
+                break;
+            }
+            before = previous.before;
+            self.entries.pop_back();
+        }
+        self.pending.push(OrbitCommand::SetPose(before));
+        self.last = Some(before);
+        Some(entry)
+    }
+
+    /// Applies `entries` again with their original spacing in time, starting
+    /// on the next update.
+    pub fn replay(&mut self, entries: impl IntoIterator<Item = LoggedCommand>) {
+        self.replay = entries.into_iter().collect();
+        self.replay_offset = None;
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        !self.replay.is_empty()
+    }
+
+    fn log(&mut self, time: f64, command: OrbitCommand, before: OrbitPose) {
+        self.entries.push_back(LoggedCommand {
+            time,
+            command,
+            before,
+        });
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+impl Default for CommandLog {
+    fn default() -> Self {
+        CommandLog::new()
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn command_log_apply_system(
+        time: Res<Time>,
+        mut query: Query<(&mut OrbitCamera, &mut CommandLog)>,
+    ) {
+        let now = time.seconds_since_startup();
+        for (mut camera, mut log) in query.iter_mut() {
+            if log.is_replaying() {
+                let first = log.replay.front().map_or(now, |entry| entry.time);
+                let offset = *log.replay_offset.get_or_insert(now - first);
+                while let Some(entry) = log.replay.front() {
+                    if entry.time + offset > now {
+                        break;
+                    }
+                    entry.command.apply(&mut camera);
+                    log.replay.pop_front();
+                }
+            }
+            for command in std::mem::take(&mut log.pending) {
+                command.apply(&mut camera);
+            }
+        }
+    }
+
+    pub fn command_log_capture_system(
+        time: Res<Time>,
+        mut query: Query<(&OrbitCamera, &mut CommandLog)>,
+    ) {
+        let now = time.seconds_since_startup();
+        for (camera, mut log) in query.iter_mut() {
+            let pose = OrbitPose::from(camera);
+            let before = match log.last {
+                Some(before) if before != pose => before,
+                Some(_) => continue,
+                None => {
+                    log.last = Some(pose);
+                    continue;
+                }
+            };
+            let rotation = Vec2::new(pose.x - before.x, pose.y - before.y);
+            if rotation != Vec2::ZERO {
+                log.log(now, OrbitCommand::Rotate(rotation), before);
+            }
+            if pose.center != before.center {
+                let mut after = before;
+                after.x = pose.x;
+                after.y = pose.y;
+                log.log(now, OrbitCommand::Pan(pose.center - before.center), after);
+            }
+            if pose.distance != before.distance {
+                let command = if before.distance > 0.0 {
+                    OrbitCommand::Zoom(pose.distance / before.distance)
+                } else {
+                    OrbitCommand::SetPose(pose)
+                };
+                let mut after = pose;
+                after.distance = before.distance;
+                log.log(now, command, after);
+            }
+            log.last = Some(pose);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Converting angles to and from other tools' conventions.
+//!
+//! Internally, `x` is the camera's heading around the Y axis, counterclockwise
+//! seen from above with zero putting the camera on the -Z side of the center,
+//! and `y` is its polar angle down from +Y.
+
+use crate::OrbitCamera;
+use std::f32::consts::{FRAC_PI_2, PI};
+
+/// The axis from the center towards the camera at zero yaw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum YawReference {
+    PositiveX,
+    NegativeX,
+    PositiveZ,
+    NegativeZ,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PitchConvention {
+    /// Angle down from straight above, like `OrbitCamera::y`.
+    Polar,
+    /// Angle up from the horizon.
+    Elevation,
+}
+
+/// How yaw and pitch are measured outside the plugin. The default matches
+/// [`OrbitCamera`]'s own `x` and `y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Conventions {
+    pub yaw_reference: YawReference,
+    /// Measure yaw clockwise seen from above, rather than counterclockwise.
+    pub yaw_clockwise: bool,
+    pub pitch: PitchConvention,
+    pub invert_pitch: bool,
+    pub degrees: bool,
+}
+
+impl Default for Conventions {
+    fn default() -> Self {
+        Conventions::INTERNAL
+    }
+}
+
+impl Conventions {
+    /// [`OrbitCamera`]'s own `x` and `y`.
+    pub const INTERNAL: Conventions = Conventions {
+        yaw_reference: YawReference::NegativeZ,
+        yaw_clockwise: false,
+        pitch: PitchConvention::Polar,
+        invert_pitch: false,
+        degrees: false,
+    };
+
+    /// Z-up style turntable angles in degrees: yaw from +X counterclockwise,
+    /// pitch as elevation above the horizon.
+    pub const ELEVATION_DEGREES: Conventions = Conventions {
+        yaw_reference: YawReference::PositiveX,
+        yaw_clockwise: false,
+        pitch: PitchConvention::Elevation,
+        invert_pitch: false,
+        degrees: true,
+    };
+
+    /// Converts `x` and `y` to yaw and pitch. Yaw is wrapped to half a turn
+    /// either side of zero.
+    pub fn to_external(&self, x: f32, y: f32) -> (f32, f32) {
+        let mut yaw = wrap(x + self.yaw_offset());
+        if self.yaw_clockwise {
+            yaw = -yaw;
+        }
+        let mut pitch = match self.pitch {
+            PitchConvention::Polar => y,
+            PitchConvention::Elevation => FRAC_PI_2 - y,
+        };
+        if self.invert_pitch {
+            pitch = -pitch;
+        }
+        if self.degrees {
+            (yaw.to_degrees(), pitch.to_degrees())
+        } else {
+            (yaw, pitch)
+        }
+    }
+
+    /// Converts yaw and pitch back to `x` and `y`.
+    pub fn from_external(&self, yaw: f32, pitch: f32) -> (f32, f32) {
+        let (mut yaw, mut pitch) = if self.degrees {
+            (yaw.to_radians(), pitch.to_radians())
+        } else {
+            (yaw, pitch)
+        };
+        if self.yaw_clockwise {
+            yaw = -yaw;
+        }
+        if self.invert_pitch {
+            pitch = -pitch;
+        }
+        let y = match self.pitch {
+            PitchConvention::Polar => pitch,
+            PitchConvention::Elevation => FRAC_PI_2 - pitch,
+        };
+        (yaw - self.yaw_offset(), y)
+    }
+
+    fn yaw_offset(&self) -> f32 {
+        match self.yaw_reference {
+            YawReference::NegativeZ => 0.0,
+            YawReference::PositiveZ => PI,
+            YawReference::PositiveX => FRAC_PI_2,
+            YawReference::NegativeX => -FRAC_PI_2,
+        }
+    }
+}
+
+fn wrap(angle: f32) -> f32 {
+    let wrapped = (angle + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped <= -PI {
+        wrapped + 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+impl OrbitCamera {
+    /// The target yaw and pitch in the given conventions.
+    pub fn angles(&self, conventions: &Conventions) -> (f32, f32) {
+        conventions.to_external(self.x, self.y)
+    }
+
+    /// Sets the target yaw and pitch from the given conventions, keeping the
+    /// pitch within `pitch_range`.
+    pub fn set_angles(&mut self, conventions: &Conventions, yaw: f32, pitch: f32) {
+        let (x, y) = conventions.from_external(yaw, pitch);
+        self.x = x;
+        self.y = y
+            .max(*self.pitch_range.start())
+            .min(*self.pitch_range.end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_angles() {
+        let clockwise = Conventions {
+            yaw_reference: YawReference::PositiveZ,
+            yaw_clockwise: true,
+            invert_pitch: true,
+            ..Conventions::INTERNAL
+        };
+        for conventions in [
+            Conventions::INTERNAL,
+            Conventions::ELEVATION_DEGREES,
+            clockwise,
+        ]
+        .iter()
+        {
+            for &(x, y) in [(0.0, 0.5), (1.0, 1.2), (-2.5, 3.0)].iter() {
+                let (yaw, pitch) = conventions.to_external(x, y);
+                let (back_x, back_y) = conventions.from_external(yaw, pitch);
+                assert!((wrap(back_x - x)).abs() < 1e-5, "{:?}", conventions);
+                assert!((back_y - y).abs() < 1e-5, "{:?}", conventions);
+            }
+        }
+    }
+
+    #[test]
+    fn converts_to_elevation_degrees() {
+        let (yaw, pitch) = Conventions::ELEVATION_DEGREES.to_external(0.0, FRAC_PI_2);
+        assert!((yaw - 90.0).abs() < 1e-4);
+        assert!(pitch.abs() < 1e-4);
+    }
+
+    #[test]
+    fn wraps_yaw_to_half_a_turn() {
+        assert!((wrap(3.0 * PI) - PI).abs() < 1e-5);
+        assert!((wrap(-PI) - PI).abs() < 1e-5);
+        assert!((wrap(0.5) - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,36 @@
+//! Distances that change with pitch.
+
+use std::f32::consts::FRAC_PI_2;
+
+/// Scales how far an orbiting camera on the same entity is from the center
+/// by a function of its pitch, like a crane that swings in close when the
+/// view is level and out when it looks straight down, to keep characters
+/// readable from every angle. The function maps [`OrbitCamera::y`] to a
+/// factor on the zoomed distance, so zooming and `distance_range` still work
+/// on the distance before the factor.
+///
+/// [`OrbitCamera::y`]: crate::OrbitCamera::y
+pub struct PitchDistance {
+    pub scale: Box<dyn Fn(f32) -> f32 + Send + Sync>,
+}
+
+impl PitchDistance {
+    pub fn new(scale: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        PitchDistance {
+            scale: Box::new(scale),
+        }
+    }
+
+    /// Scales by `top_down` when looking straight down and `level` when
+    /// level with the center, blending smoothly in between.
+    pub fn between(top_down: f32, level: f32) -> Self {
+        PitchDistance::new(move |y| {
+            let t = (y / FRAC_PI_2).clamp(0.0, 1.0);
+            top_down + (level - top_down) * t * t * (3.0 - 2.0 * t)
+        })
+    }
+
+    pub fn scale(&self, y: f32) -> f32 {
+        (self.scale)(y).max(0.0)
+    }
+}
@@ -0,0 +1,146 @@
+//! Stepping the orbit center through a list of entities.
+
+use crate::focusable::focus_transition;
+use crate::split_screen::receives_input;
+use crate::{
+    ActiveOrbitCamera, CameraHistory, Focusable, InputCapture, OrbitBounds, OrbitCamera,
+    OrbitCameraPlugin, OrbitPose, OrbitTransition,
+};
+use bevy::prelude::*;
+use bevy::render::camera::{Camera, PerspectiveProjection};
+
+/// Moves the orbit center of the camera on the same entity from one of
+/// `targets` to the next, keeping the current angles and distance unless the
+/// target is [`Focusable`]. `key` steps forward, and backward while Shift is
+/// held. Each move is remembered by any [`CameraHistory`], so it can be undone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FocusCycle {
+    pub targets: Vec<Entity>,
+    pub key: Option<KeyCode>,
+    /// How long, in seconds, moving to the next target takes.
+    pub duration: f32,
+    current: Option<usize>,
+    pending: isize,
+}
+
+impl FocusCycle {
+    pub fn new(targets: Vec<Entity>) -> Self {
+        FocusCycle {
+            targets,
+            key: Some(KeyCode::Tab),
+            duration: 0.4,
+            current: None,
+            pending: 0,
+        }
+    }
+
+    /// The target last moved to.
+    pub fn current(&self) -> Option<Entity> {
+        self.current
+            .and_then(|index| self.targets.get(index).copied())
+    }
+
+    /// Moves `dir` targets forward, or backward when negative, on the next
+    /// update, wrapping around the ends of the list.
+    pub fn cycle_targets(&mut self, dir: isize) {
+        self.pending += dir;
+    }
+}
+
+impl Default for FocusCycle {
+    fn default() -> Self {
+        FocusCycle::new(Vec::new())
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn focus_cycle_system(
+        mut commands: Commands,
+        keyboard_input: Res<Input<KeyCode>>,
+        capture: Res<InputCapture>,
+        windows: Res<Windows>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        targets: Query<(&GlobalTransform, Option<&OrbitBounds>, Option<&Focusable>)>,
+        mut query: Query<(
+            Entity,
+            &OrbitCamera,
+            &mut FocusCycle,
+            Option<&OrbitTransition>,
+            Option<&PerspectiveProjection>,
+            Option<&Camera>,
+            Option<&mut CameraHistory>,
+        )>,
+    ) {
+        let shift =
+            keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift);
+        for (entity, camera, mut cycle, transition, projection, render_camera, history) in
+            query.iter_mut()
+        {
+            if !camera.enabled {
+                cycle.pending = 0;
+                continue;
+            }
+            let pressed = cycle
+                .key
+                .map_or(false, |key| keyboard_input.just_pressed(key));
+            let routed = !capture.keyboard
+                && receives_input(active.as_deref(), entity)
+                && camera.input_area.has_keyboard(&windows, render_camera);
+            if pressed && routed {
+                cycle.cycle_targets(if shift { -1 } else { 1 });
+            }
+            let dir = std::mem::take(&mut cycle.pending);
+            let len = cycle.targets.len() as isize;
+            if dir == 0 || len == 0 {
+                continue;
+            }
+
+            // Despawned targets are skipped rather than removed, so indices
+            // stay stable while the app updates the list.
+            let start = match cycle.current {
+                Some(index) => index as isize,
+                None if dir > 0 => -1,
+                None => len,
+            };
+            let sign = dir.signum();
+            let mut index = start;
+            let mut remaining = dir.abs();
+            let mut found = None;
+            for _ in 0..len * dir.abs() {
+                index = (index + sign).rem_euclid(len);
+                if let Ok(target) = targets.get(cycle.targets[index as usize]) {
+                    remaining -= 1;
+                    if remaining == 0 {
+                        found = Some((index as usize, target));
+                        break;
+                    }
+                }
+            }
+            let (index, (transform, bounds, focusable)) = match found {
+                Some(found) => found,
+                None => continue,
+            };
+            cycle.current = Some(index);
+
+            // Keep heading wherever a running transition was going.
+            let mut step = focus_transition(
+                camera,
+                projection,
+                transform,
+                bounds,
+                focusable,
+                cycle.duration,
+            );
+            if let Some(transition) = transition {
+                step.x = step.x.or(transition.x);
+                step.y = step.y.or(transition.y);
+                step.distance = step.distance.or(transition.distance);
+            }
+            if let Some(mut history) = history {
+                history.push(OrbitPose::from(camera));
+            }
+            commands.entity(entity).insert(step);
+        }
+    }
+}
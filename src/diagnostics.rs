@@ -0,0 +1,69 @@
+//! Camera metrics for Bevy's diagnostics.
+
+use crate::{
+    CameraEvents, OrbitCameraState, OrbitCameraSystem, PrimaryOrbitCamera, RoutedCameraEvents,
+};
+use bevy::diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy::prelude::*;
+
+/// Reports the distance and angular speed of the [`PrimaryOrbitCamera`], or
+/// else of the first camera found, and how many [`CameraEvents`] and
+/// [`RoutedCameraEvents`] were sent, so they show up in `LogDiagnosticsPlugin`
+/// next to the frame rate.
+#[derive(Default)]
+pub struct OrbitCameraDiagnosticsPlugin;
+
+impl OrbitCameraDiagnosticsPlugin {
+    pub const DISTANCE: DiagnosticId =
+        DiagnosticId::from_u128(121115619519990651140876094967549721634);
+    pub const ANGULAR_VELOCITY: DiagnosticId =
+        DiagnosticId::from_u128(183347445729678248962548199940978446513);
+    pub const EVENTS: DiagnosticId =
+        DiagnosticId::from_u128(17429526315126310245515835885616661442);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::DISTANCE, "orbit_camera_distance", 20));
+        diagnostics.add(
+            Diagnostic::new(Self::ANGULAR_VELOCITY, "orbit_camera_angular_velocity", 20)
+                .with_suffix("rad/s"),
+        );
+        diagnostics.add(Diagnostic::new(Self::EVENTS, "orbit_camera_events", 20));
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        time: Res<Time>,
+        primary: Option<Res<PrimaryOrbitCamera>>,
+        mut events: EventReader<CameraEvents>,
+        mut routed: EventReader<RoutedCameraEvents>,
+        query: Query<(Entity, &OrbitCameraState)>,
+    ) {
+        let count = events.iter().count() + routed.iter().count();
+        diagnostics.add_measurement(Self::EVENTS, count as f64);
+
+        let primary = primary.and_then(|primary| primary.entity);
+        let state = match primary {
+            Some(entity) => query.get(entity).ok().map(|(_, state)| state),
+            None => query.iter().next().map(|(_, state)| state),
+        };
+        if let Some(state) = state {
+            diagnostics.add_measurement(Self::DISTANCE, state.distance as f64);
+            let dt = time.delta_seconds();
+            if dt > 0.0 {
+                let speed = state.last_rotation_delta.length() / dt;
+                diagnostics.add_measurement(Self::ANGULAR_VELOCITY, speed as f64);
+            }
+        }
+    }
+}
+
+impl Plugin for OrbitCameraDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_startup_system(Self::setup_system.system())
+            .add_system(
+                Self::diagnostic_system
+                    .system()
+                    .after(OrbitCameraSystem::Smoothing),
+            );
+    }
+}
@@ -0,0 +1,85 @@
+//! The dolly zoom, or Vertigo effect.
+
+use crate::{CameraSuspended, OrbitCamera, OrbitCameraPlugin, OrbitCameraState};
+use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+
+/// Animates the field of view of a perspective camera to `fov` while moving
+/// it so the scene at the orbit center stays `width_at_center` wide on
+/// screen, then removes itself. Distance and field of view skip smoothing so
+/// they stay in step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DollyZoom {
+    pub width_at_center: f32,
+    pub fov: f32,
+    pub duration: f32,
+    elapsed: f32,
+    start_fov: Option<f32>,
+}
+
+impl DollyZoom {
+    pub fn new(width_at_center: f32, fov: f32, duration: f32) -> Self {
+        DollyZoom {
+            width_at_center,
+            fov,
+            duration,
+            elapsed: 0.0,
+            start_fov: None,
+        }
+    }
+
+    /// Progress through the zoom, from zero to one.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        }
+    }
+}
+
+/// The distance at which a view with vertical field of view `fov` is `width`
+/// wide.
+pub fn distance_for_width(width: f32, fov: f32, aspect_ratio: f32) -> f32 {
+    width / (2.0 * (fov / 2.0).tan() * aspect_ratio)
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn dolly_zoom_system(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut query: Query<
+            (
+                Entity,
+                &mut OrbitCamera,
+                Option<&mut OrbitCameraState>,
+                &PerspectiveProjection,
+                &mut DollyZoom,
+            ),
+            Without<CameraSuspended>,
+        >,
+    ) {
+        for (entity, mut camera, state, projection, mut dolly) in query.iter_mut() {
+            let current = camera.fov.unwrap_or(projection.fov);
+            let start = *dolly.start_fov.get_or_insert(current);
+            dolly.elapsed += time.delta_seconds();
+            let t = dolly.progress();
+            let eased = t * t * (3.0 - 2.0 * t);
+            let fov = start + (dolly.fov - start) * eased;
+            let distance = distance_for_width(dolly.width_at_center, fov, projection.aspect_ratio);
+
+            camera.fov = Some(fov);
+            if distance.is_finite() && distance > 0.0 {
+                camera.distance = distance;
+            }
+            if let Some(mut state) = state {
+                state.fov = camera.fov;
+                state.distance = camera.distance;
+            }
+            if t >= 1.0 {
+                commands.entity(entity).remove::<DollyZoom>();
+            }
+        }
+    }
+}
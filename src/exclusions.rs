@@ -0,0 +1,91 @@
+//! Parts of the window where a camera ignores input.
+
+use crate::{OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// Identifies a rectangle added to [`InputExclusions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExclusionId(u32);
+
+/// Rectangles of the camera's window, in logical pixels from the bottom-left
+/// corner like `Window::cursor_position`, where the camera on the same entity
+/// ignores the mouse and touch, such as over a toolbar drawn by custom UI.
+/// Drags and touches that start inside a rectangle are ignored until they
+/// end, even once they leave it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputExclusions {
+    rects: Vec<(ExclusionId, Vec2, Vec2)>,
+    next_id: u32,
+    drag_blocked: bool,
+}
+
+impl InputExclusions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rectangle between the corners `min` and `max`.
+    pub fn add(&mut self, min: Vec2, max: Vec2) -> ExclusionId {
+        let id = ExclusionId(self.next_id);
+        self.next_id += 1;
+        self.rects.push((id, min.min(max), min.max(max)));
+        id
+    }
+
+    /// Removes a rectangle, returning whether it was there.
+    pub fn remove(&mut self, id: ExclusionId) -> bool {
+        let len = self.rects.len();
+        self.rects.retain(|(rect, _, _)| *rect != id);
+        self.rects.len() != len
+    }
+
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    pub fn contains(&self, position: Vec2) -> bool {
+        self.rects
+            .iter()
+            .any(|(_, min, max)| position.cmpge(*min).all() && position.cmple(*max).all())
+    }
+
+    /// Whether the current mouse drag started inside a rectangle.
+    pub fn is_drag_blocked(&self) -> bool {
+        self.drag_blocked
+    }
+}
+
+/// Where the cursor is over the window `camera` draws to, if it is.
+pub(crate) fn cursor_position(windows: &Windows, camera: Option<&Camera>) -> Option<Vec2> {
+    windows.get(camera?.window)?.cursor_position()
+}
+
+impl OrbitCameraPlugin {
+    pub fn input_exclusions_system(
+        mouse_button_input: Res<Input<MouseButton>>,
+        windows: Res<Windows>,
+        mut query: Query<(&OrbitCamera, &mut InputExclusions, Option<&Camera>)>,
+    ) {
+        for (camera, mut exclusions, render_camera) in query.iter_mut() {
+            let buttons = camera.drag_buttons();
+            if !buttons
+                .iter()
+                .any(|button| mouse_button_input.pressed(*button))
+            {
+                if exclusions.drag_blocked {
+                    exclusions.drag_blocked = false;
+                }
+            } else if buttons
+                .iter()
+                .any(|button| mouse_button_input.just_pressed(*button))
+            {
+                let inside = cursor_position(&windows, render_camera)
+                    .map_or(false, |position| exclusions.contains(position));
+                if inside != exclusions.drag_blocked {
+                    exclusions.drag_blocked = inside;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Events for tactile and audible cues.
+
+use bevy::prelude::*;
+
+/// Sent when the camera does something the user may want to feel or hear,
+/// such as a gamepad rumble when a limit stops it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraFeedback {
+    pub camera: Entity,
+    pub kind: FeedbackKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FeedbackKind {
+    /// Input pushed the camera past a limit, which held it back. Sent every
+    /// frame the limit holds.
+    LimitReached(CameraLimit),
+    /// The camera turned by a fixed step, such as with [`KeyboardSteps`]
+    /// or a [`FlickTurn`].
+    ///
+    /// [`KeyboardSteps`]: crate::KeyboardSteps
+    /// [`FlickTurn`]: crate::FlickTurn
+    SnapEngaged,
+    /// A move was undone because it left where the camera may go, such as
+    /// the [`WalkSurface`](crate::WalkSurface) of a walking camera.
+    CollisionCorrected,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CameraLimit {
+    Pitch,
+    Yaw,
+    Distance,
+    /// The field of view or orthographic scale range.
+    Zoom,
+    /// The bounds of the scene.
+    Bounds,
+}
+
+impl CameraFeedback {
+    pub(crate) fn limit(camera: Entity, limit: CameraLimit) -> Self {
+        CameraFeedback {
+            camera,
+            kind: FeedbackKind::LimitReached(limit),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Per-camera filtering of control events.
+
+use crate::CameraEvents;
+
+/// Vetoes or rewrites the [`CameraEvents`] reaching the camera on the same
+/// entity. Returning `None` drops the event.
+///
+/// ```no_compile
+/// // Block zooming, and snap rotation to whole pixels.
+/// InputFilter::new(|event| match event {
+///     CameraEvents::Zoom(_) => None,
+///     CameraEvents::Orbit(delta) => Some(CameraEvents::Orbit(delta.round())),
+///     event => Some(event),
+/// })
+/// ```
+pub struct InputFilter(pub Box<dyn Fn(CameraEvents) -> Option<CameraEvents> + Send + Sync>);
+
+impl InputFilter {
+    pub fn new(
+        filter: impl Fn(CameraEvents) -> Option<CameraEvents> + Send + Sync + 'static,
+    ) -> Self {
+        InputFilter(Box::new(filter))
+    }
+
+    pub fn apply(&self, event: CameraEvents) -> Option<CameraEvents> {
+        (self.0)(event)
+    }
+}
+
+/// Runs `event` through the filter, if there is one.
+pub(crate) fn filtered(filter: Option<&InputFilter>, event: &CameraEvents) -> Option<CameraEvents> {
+    match filter {
+        Some(filter) => filter.apply(*event),
+        None => Some(*event),
+    }
+}
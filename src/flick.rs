@@ -0,0 +1,155 @@
+//! Quick turns from a fast flick.
+
+use crate::split_screen::receives_input;
+use crate::{
+    ActiveOrbitCamera, CameraFeedback, CameraMode, ControlScheme, FeedbackKind, InputCapture,
+    OrbitCamera, OrbitCameraPlugin, OrbitTransition, UiPointerCapture,
+};
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Press {
+    started: f64,
+    x: f32,
+    motion: Vec2,
+    touch: Option<u64>,
+}
+
+/// Turns the camera on the same entity by `angle` around the center when a
+/// rotating drag, with the mouse or one finger, is short and fast, such as a
+/// 180° look behind in third-person games.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlickTurn {
+    pub angle: f32,
+    /// Longest the drag may last, in seconds.
+    pub max_duration: f32,
+    /// Shortest horizontal drag, in pixels, that counts as a flick.
+    pub min_distance: f32,
+    /// How long, in seconds, the turn takes.
+    pub duration: f32,
+    press: Option<Press>,
+}
+
+impl FlickTurn {
+    pub fn new(angle: f32) -> Self {
+        FlickTurn {
+            angle,
+            max_duration: 0.2,
+            min_distance: 60.0,
+            duration: 0.35,
+            press: None,
+        }
+    }
+
+    fn flick(&self, press: &Press, now: f64) -> Option<f32> {
+        let motion = press.motion;
+        if now - press.started > self.max_duration as f64
+            || motion.x.abs() < self.min_distance
+            || motion.x.abs() < motion.y.abs()
+        {
+            return None;
+        }
+        // Dragging right turns the same way it does when rotating normally.
+        Some(press.x - motion.x.signum() * self.angle)
+    }
+}
+
+impl Default for FlickTurn {
+    fn default() -> Self {
+        FlickTurn::new(std::f32::consts::PI)
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::too_many_arguments)]
+    pub fn flick_turn_system(
+        mut commands: Commands,
+        mut feedback: EventWriter<CameraFeedback>,
+        time: Res<Time>,
+        mouse_button_input: Res<Input<MouseButton>>,
+        keyboard_input: Res<Input<KeyCode>>,
+        mut mouse_motion_events: EventReader<MouseMotion>,
+        touches: Res<Touches>,
+        capture: Res<InputCapture>,
+        ui: Res<UiPointerCapture>,
+        windows: Res<Windows>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        mut query: Query<(Entity, &OrbitCamera, &mut FlickTurn, Option<&Camera>)>,
+    ) {
+        let now = time.seconds_since_startup();
+        let mut mouse_motion = Vec2::ZERO;
+        for event in mouse_motion_events.iter() {
+            mouse_motion += event.delta;
+        }
+        let touch_count = touches.iter().count();
+        let taken = capture.pointer_taken(&ui);
+
+        for (entity, camera, mut flick, render_camera) in query.iter_mut() {
+            let map = camera.mode == CameraMode::Map;
+            let receives = receives_input(active.as_deref(), entity);
+            if !camera.enabled || camera.rotation_locked || map || taken || !receives {
+                flick.press = None;
+                continue;
+            }
+            let mut released = None;
+            match flick.press {
+                Some(mut press) => match press.touch {
+                    Some(id) => {
+                        if let Some(touch) = touches.get_released(id) {
+                            press.motion = touch.distance();
+                            released = Some(press);
+                            flick.press = None;
+                        } else if touch_count > 1 || touches.get_pressed(id).is_none() {
+                            flick.press = None;
+                        }
+                    }
+                    None => {
+                        press.motion += mouse_motion;
+                        if mouse_button_input.just_released(camera.rotate_binding.button) {
+                            released = Some(press);
+                            flick.press = None;
+                        } else {
+                            flick.press = Some(press);
+                        }
+                    }
+                },
+                None => {
+                    let touch = touches.iter_just_pressed().next();
+                    let drags_rotate = camera.control_scheme == ControlScheme::Standard;
+                    let pressed = mouse_button_input.just_pressed(camera.rotate_binding.button)
+                        && camera.drags(&mouse_button_input, &keyboard_input).rotate
+                        && camera.input_area.has_cursor(&windows, render_camera);
+                    let touch =
+                        touch.filter(|touch| camera.input_area.contains(touch.start_position()));
+                    if drags_rotate && pressed {
+                        flick.press = Some(Press {
+                            started: now,
+                            x: camera.x,
+                            motion: Vec2::ZERO,
+                            touch: None,
+                        });
+                    } else if let (Some(touch), 1) = (touch, touch_count) {
+                        flick.press = Some(Press {
+                            started: now,
+                            x: camera.x,
+                            motion: Vec2::ZERO,
+                            touch: Some(touch.id()),
+                        });
+                    }
+                }
+            }
+
+            if let Some(x) = released.and_then(|press| flick.flick(&press, now)) {
+                let mut turn = OrbitTransition::new(flick.duration);
+                turn.x = Some(x);
+                commands.entity(entity).insert(turn);
+                feedback.send(CameraFeedback {
+                    camera: entity,
+                    kind: FeedbackKind::SnapEngaged,
+                });
+            }
+        }
+    }
+}
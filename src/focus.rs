@@ -0,0 +1,131 @@
+//! Double-click to focus.
+//!
+//! Double-clicking sends a [`FocusRequested`] for every enabled camera in the
+//! window under the cursor, whether or not the [`Raycaster`] found a world
+//! position, so apps can also resolve the click with their own picking.
+//! Requests carrying a world position move the orbit center there.
+
+use crate::ray::pick;
+use crate::split_screen::receives_input;
+use crate::{
+    ActiveOrbitCamera, CameraHistory, InputCapture, InputExclusions, OrbitCamera,
+    OrbitCameraPlugin, OrbitPose, OrbitTransition, Raycaster, UiPointerCapture,
+};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoubleClickSettings {
+    pub button: MouseButton,
+    /// Longest gap, in seconds, between the clicks.
+    pub interval: f32,
+    /// Furthest the cursor may move, in pixels, between the clicks.
+    pub max_movement: f32,
+    /// How long, in seconds, the orbit center takes to reach the focus.
+    pub focus_duration: f32,
+}
+
+impl Default for DoubleClickSettings {
+    fn default() -> Self {
+        DoubleClickSettings {
+            button: MouseButton::Left,
+            interval: 0.3,
+            max_movement: 5.0,
+            focus_duration: 0.4,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusRequested {
+    pub camera: Entity,
+    pub screen_position: Vec2,
+    /// The point under the cursor, if one could be resolved.
+    pub world_position: Option<Vec3>,
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::too_many_arguments)]
+    pub fn emit_focus_events(
+        mut events: EventWriter<FocusRequested>,
+        mut last_click: Local<Option<(f64, Vec2)>>,
+        time: Res<Time>,
+        windows: Res<Windows>,
+        raycaster: Res<Raycaster>,
+        mouse_button_input: Res<Input<MouseButton>>,
+        settings: Res<DoubleClickSettings>,
+        capture: Res<InputCapture>,
+        ui: Res<UiPointerCapture>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        query: Query<(
+            Entity,
+            &OrbitCamera,
+            &Camera,
+            &GlobalTransform,
+            Option<&InputExclusions>,
+        )>,
+    ) {
+        if capture.pointer_taken(&ui) || !mouse_button_input.just_pressed(settings.button) {
+            return;
+        }
+        let (window, position) = match windows
+            .iter()
+            .find_map(|window| window.cursor_position().map(|pos| (window.id(), pos)))
+        {
+            Some(cursor) => cursor,
+            None => return,
+        };
+        let now = time.seconds_since_startup();
+        match *last_click {
+            Some((time, last))
+                if now - time <= settings.interval as f64
+                    && last.distance(position) <= settings.max_movement =>
+            {
+                *last_click = None;
+            }
+            _ => {
+                *last_click = Some((now, position));
+                return;
+            }
+        }
+
+        for (entity, orbit, camera, transform, exclusions) in query.iter() {
+            let excluded = exclusions.map_or(false, |exclusions| exclusions.contains(position));
+            if orbit.enabled
+                && camera.window == window
+                && orbit.input_area.contains(position)
+                && receives_input(active.as_deref(), entity)
+                && !excluded
+            {
+                events.send(FocusRequested {
+                    camera: entity,
+                    screen_position: position,
+                    world_position: pick(
+                        &raycaster, entity, orbit, camera, transform, &windows, position,
+                    ),
+                });
+            }
+        }
+    }
+
+    pub fn focus_system(
+        mut commands: Commands,
+        mut events: EventReader<FocusRequested>,
+        settings: Res<DoubleClickSettings>,
+        mut history: Query<(&OrbitCamera, &mut CameraHistory)>,
+    ) {
+        for event in events.iter() {
+            if let Some(world_position) = event.world_position {
+                if let Ok((camera, mut history)) = history.get_mut(event.camera) {
+                    history.push(OrbitPose::from(camera));
+                }
+                commands
+                    .entity(event.camera)
+                    .insert(OrbitTransition::to_center(
+                        world_position,
+                        settings.focus_duration,
+                    ));
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! How individual entities want to be framed when focused.
+
+use crate::{framing_distance, OrbitBounds, OrbitCamera, OrbitTransition};
+use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+
+/// Overrides how the camera frames this entity when focusing it. Without a
+/// `preferred_distance`, entities with [`OrbitBounds`] are framed to fit the
+/// view with `padding` extra room, as a fraction of their radius.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Focusable {
+    pub preferred_distance: Option<f32>,
+    /// The pitch to look at the entity from, in the internal convention of
+    /// [`OrbitCamera::y`].
+    pub preferred_pitch: Option<f32>,
+    pub padding: f32,
+}
+
+/// A transition centering `camera` on an entity placed by `transform`,
+/// framed as its [`Focusable`] asks.
+pub(crate) fn focus_transition(
+    camera: &OrbitCamera,
+    projection: Option<&PerspectiveProjection>,
+    transform: &GlobalTransform,
+    bounds: Option<&OrbitBounds>,
+    focusable: Option<&Focusable>,
+    duration: f32,
+) -> OrbitTransition {
+    let sphere = bounds.map(|bounds| bounds.bounding_sphere(transform));
+    let center = sphere.map_or(transform.translation, |(center, _)| center);
+    let mut transition = OrbitTransition::to_center(center, duration);
+    let focusable = match focusable {
+        Some(focusable) => focusable,
+        None => return transition,
+    };
+    transition.y = focusable.preferred_pitch.map(|pitch| {
+        pitch
+            .max(*camera.pitch_range.start())
+            .min(*camera.pitch_range.end())
+    });
+    transition.distance = focusable.preferred_distance.or_else(|| {
+        let (_, radius) = sphere?;
+        let projection = projection?;
+        let fov = camera.fov.unwrap_or(projection.fov);
+        Some(framing_distance(
+            (center, radius * (1.0 + focusable.padding)),
+            center,
+            fov,
+            projection.aspect_ratio,
+        ))
+    });
+    transition
+}
@@ -0,0 +1,506 @@
+//! Keeping the orbit center on a moving entity.
+
+use crate::pivot::{orbit_rotation, up_rotation};
+use crate::{
+    CameraCollision, CameraMode, CameraSuspended, OrbitCamera, OrbitCameraPlugin, OrbitCameraState,
+    Ray, Raycaster, Smoothing,
+};
+use bevy::core::FixedTimesteps;
+use bevy::prelude::*;
+
+/// What a [`FollowTarget`] does when its target teleports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TeleportResponse {
+    /// Jump to the new position at once instead of sweeping across.
+    Snap,
+    /// Jump halfway through a fade of this many seconds, which the app draws
+    /// when it receives the [`TargetTeleported`].
+    Fade(f32),
+}
+
+/// What a [`FollowTarget`] does once its target is despawned. The component
+/// is removed and a [`TargetLost`] sent in every case.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TargetLostResponse {
+    /// Keep the center where the target was last seen.
+    Freeze,
+    /// Move the center back to this point.
+    Fallback(Vec3),
+    /// Turn off the camera's controls.
+    Disable,
+}
+
+/// How a [`TargetPrediction`] places the target between fixed steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PredictionMode {
+    /// Blends from the previous step's position to the latest one, which
+    /// shows the target a step late but only where it really was.
+    Interpolate,
+    /// Carries on from the latest position at the last step's velocity, which
+    /// keeps up with the target but overshoots when it turns or stops.
+    Extrapolate,
+}
+
+/// Follows a target moved in a `FixedTimestep`, such as by physics, as it
+/// would be at the time of the frame, so the camera doesn't judder when
+/// frames and steps don't line up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetPrediction {
+    /// The label given to the `FixedTimestep` that moves the target.
+    pub timestep: String,
+    pub mode: PredictionMode,
+}
+
+/// Shifts a [`FollowTarget`]'s center ahead of its target, so players see
+/// more of where they're going.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LookAhead {
+    /// Seconds of the target's velocity to look ahead by.
+    pub time: f32,
+    /// The furthest, in world units, the center is shifted.
+    pub max_distance: f32,
+    /// When set, used instead of the target's velocity, such as the
+    /// direction the player is steering in. Its length, up to one, scales
+    /// `max_distance`.
+    pub direction: Option<Vec3>,
+    /// The time constant, in seconds, of easing the shift.
+    pub tau: f32,
+    shift: Vec3,
+    last: Option<Vec3>,
+}
+
+impl LookAhead {
+    pub fn new(time: f32, max_distance: f32) -> Self {
+        LookAhead {
+            time,
+            max_distance,
+            direction: None,
+            tau: 0.5,
+            shift: Vec3::ZERO,
+            last: None,
+        }
+    }
+
+    /// How far the center is shifted.
+    pub fn shift(&self) -> Vec3 {
+        self.shift
+    }
+
+    /// Eases the shift towards where the target at `position` is heading.
+    fn update(&mut self, position: Vec3, dt: f32) -> Vec3 {
+        let target = match (self.direction, self.last) {
+            (Some(direction), _) => direction.clamp_length_max(1.0) * self.max_distance,
+            (None, Some(last)) if dt > 0.0 => {
+                ((position - last) / dt * self.time).clamp_length_max(self.max_distance)
+            }
+            (None, _) => self.shift,
+        };
+        self.last = Some(position);
+        self.shift += (target - self.shift) * Smoothing::factor(self.tau, dt);
+        self.shift
+    }
+
+    fn reset(&mut self) {
+        self.shift = Vec3::ZERO;
+        self.last = None;
+    }
+
+    /// Moves the remembered positions along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        if let Some(last) = &mut self.last {
+            *last += offset;
+        }
+    }
+}
+
+impl Default for LookAhead {
+    fn default() -> Self {
+        LookAhead::new(0.5, 3.0)
+    }
+}
+
+/// Pitches a [`FollowTarget`]'s camera as its target rises and falls, such as
+/// when jumping or flying, to keep it framed. Only the change is applied, so
+/// manual pitching keeps working; it eases the extra pitch back out, and
+/// holds off for `resume_after` seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PitchEasing {
+    /// Radians of pitch per world unit per second the target rises or falls.
+    pub strength: f32,
+    /// The most pitch, in radians, added either way.
+    pub max_angle: f32,
+    /// The time constant, in seconds, of easing the pitch.
+    pub tau: f32,
+    pub resume_after: f32,
+    applied: f32,
+    last: Option<Vec3>,
+    written: Option<f32>,
+    paused: f32,
+}
+
+impl PitchEasing {
+    pub fn new(strength: f32) -> Self {
+        PitchEasing {
+            strength,
+            max_angle: 0.4,
+            tau: 0.3,
+            resume_after: 1.0,
+            applied: 0.0,
+            last: None,
+            written: None,
+            paused: 0.0,
+        }
+    }
+
+    fn update(&mut self, camera: &mut OrbitCamera, position: Vec3, dt: f32) {
+        if self.written.map_or(false, |y| y != camera.y) {
+            self.paused = self.resume_after;
+        }
+        let rising = match self.last {
+            Some(last) if dt > 0.0 => (position - last).dot(camera.up.normalize_or_zero()) / dt,
+            _ => 0.0,
+        };
+        self.last = Some(position);
+        let target = if self.paused > 0.0 {
+            self.paused -= dt;
+            0.0
+        } else {
+            (rising * self.strength).clamp(-self.max_angle, self.max_angle)
+        };
+        let eased = self.applied + (target - self.applied) * Smoothing::factor(self.tau, dt);
+        // Rising looks up from below, which is a larger `y`.
+        camera.y = (camera.y + eased - self.applied)
+            .max(*camera.pitch_range.start())
+            .min(*camera.pitch_range.end());
+        self.applied = eased;
+        self.written = Some(camera.y);
+    }
+}
+
+impl Default for PitchEasing {
+    fn default() -> Self {
+        PitchEasing::new(0.05)
+    }
+}
+
+/// Yaws a [`FollowTarget`]'s camera gently away from nearby walls before
+/// they come between it and the target, for steering third-person cameras
+/// along corridors. It casts with the [`Raycaster`] towards where the eye
+/// would be either side of its yaw, and needs a [`CameraCollision`] on the
+/// camera for its radius. Like [`PitchEasing`], it holds off for
+/// `resume_after` seconds after the yaw is changed by anything else.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Whiskers {
+    /// Radians either side of the yaw the whiskers probe at.
+    pub angle: f32,
+    /// Radians per second turned while one whisker is blocked right at the
+    /// center and the other is clear.
+    pub turn_speed: f32,
+    pub resume_after: f32,
+    written: Option<f32>,
+    paused: f32,
+}
+
+impl Whiskers {
+    pub fn new(angle: f32) -> Self {
+        Whiskers {
+            angle,
+            turn_speed: 0.8,
+            resume_after: 1.0,
+            written: None,
+            paused: 0.0,
+        }
+    }
+
+    fn update(
+        &mut self,
+        camera: &mut OrbitCamera,
+        collision: &CameraCollision,
+        raycaster: &Raycaster,
+        dt: f32,
+    ) {
+        if self.written.map_or(false, |x| x != camera.x) {
+            self.paused = self.resume_after;
+        }
+        if self.paused > 0.0 {
+            self.paused -= dt;
+            self.written = Some(camera.x);
+            return;
+        }
+        let reach = camera.distance + collision.radius;
+        if reach <= 0.0 {
+            return;
+        }
+        // How far into the whisker a wall is, from zero when clear to one at
+        // the center.
+        let blocked = |x: f32| {
+            let direction = up_rotation(camera.up) * (orbit_rotation(x, camera.y) * Vec3::Y);
+            let ray = Ray {
+                origin: camera.center,
+                direction,
+            };
+            raycaster
+                .cast(&ray, reach)
+                .map_or(0.0, |hit| 1.0 - hit.distance / reach)
+        };
+        let steer = blocked(camera.x - self.angle) - blocked(camera.x + self.angle);
+        if steer != 0.0 {
+            camera.x = (camera.x + steer * self.turn_speed * dt)
+                .max(*camera.yaw_range.start())
+                .min(*camera.yaw_range.end());
+        }
+        self.written = Some(camera.x);
+    }
+}
+
+impl Default for Whiskers {
+    fn default() -> Self {
+        Whiskers::new(0.35)
+    }
+}
+
+/// Moves the orbit center of the camera on the same entity along with
+/// `target`, keeping the angles and distance. Top-level targets are followed
+/// by their `Transform`, so the camera doesn't lag a frame behind systems that
+/// move them before [`OrbitCameraSystem::Update`](crate::OrbitCameraSystem);
+/// children are followed by their `GlobalTransform`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FollowTarget {
+    pub target: Entity,
+    /// Added to the target's translation.
+    pub offset: Vec3,
+    /// Moves further than this in one frame are treated as teleports rather
+    /// than smoothed, if set.
+    pub teleport_threshold: Option<f32>,
+    pub teleport_response: TeleportResponse,
+    pub on_target_lost: TargetLostResponse,
+    pub prediction: Option<TargetPrediction>,
+    pub look_ahead: Option<LookAhead>,
+    pub pitch_easing: Option<PitchEasing>,
+    /// Steers around walls, with the camera's [`CameraCollision`].
+    pub whiskers: Option<Whiskers>,
+    last: Option<Vec3>,
+    pending: Option<f32>,
+    steps: Option<FixedSteps>,
+}
+
+/// The target's positions after the last two fixed steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FixedSteps {
+    previous: Vec3,
+    current: Vec3,
+    overstep: f32,
+}
+
+impl FollowTarget {
+    pub fn new(target: Entity) -> Self {
+        FollowTarget {
+            target,
+            offset: Vec3::ZERO,
+            teleport_threshold: Some(10.0),
+            teleport_response: TeleportResponse::Snap,
+            on_target_lost: TargetLostResponse::Freeze,
+            prediction: None,
+            look_ahead: None,
+            pitch_easing: None,
+            whiskers: None,
+            last: None,
+            pending: None,
+            steps: None,
+        }
+    }
+
+    /// Where the target at `position` is shown this frame, recording it as a
+    /// step if one has run since the last frame.
+    fn predict(&mut self, position: Vec3, timesteps: &FixedTimesteps) -> Vec3 {
+        let prediction = match &self.prediction {
+            Some(prediction) => prediction,
+            None => return position,
+        };
+        let overstep = match timesteps.get(&prediction.timestep) {
+            Some(state) => state.overstep_percentage() as f32,
+            None => return position,
+        };
+        let mut steps = self.steps.unwrap_or(FixedSteps {
+            previous: position,
+            current: position,
+            overstep,
+        });
+        // A step ran if the target moved or the time left over went down.
+        if position != steps.current || overstep < steps.overstep {
+            steps.previous = steps.current;
+            steps.current = position;
+        }
+        steps.overstep = overstep;
+        self.steps = Some(steps);
+        match prediction.mode {
+            PredictionMode::Interpolate => steps.previous.lerp(steps.current, overstep),
+            PredictionMode::Extrapolate => {
+                steps.current + (steps.current - steps.previous) * overstep
+            }
+        }
+    }
+
+    /// Whether the camera is waiting for a fade to cover a teleport.
+    pub fn is_fading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Moves the remembered positions along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        if let Some(last) = &mut self.last {
+            *last += offset;
+        }
+        if let Some(steps) = &mut self.steps {
+            steps.previous += offset;
+            steps.current += offset;
+        }
+        if let Some(look_ahead) = &mut self.look_ahead {
+            look_ahead.shift_origin(offset);
+        }
+        if let Some(easing) = &mut self.pitch_easing {
+            if let Some(last) = &mut easing.last {
+                *last += offset;
+            }
+        }
+    }
+}
+
+/// Sent when the target of a [`FollowTarget`] teleports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TargetTeleported {
+    pub camera: Entity,
+    pub from: Vec3,
+    pub to: Vec3,
+    /// The length of the fade to draw, for [`TeleportResponse::Fade`].
+    pub fade: Option<f32>,
+}
+
+/// Sent when the target of a [`FollowTarget`] no longer exists.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TargetLost {
+    pub camera: Entity,
+    pub target: Entity,
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn follow_target_system(
+        mut commands: Commands,
+        time: Res<Time>,
+        timesteps: Res<FixedTimesteps>,
+        mut teleported: EventWriter<TargetTeleported>,
+        mut lost: EventWriter<TargetLost>,
+        targets: Query<(&GlobalTransform, Option<&Transform>, Option<&Parent>)>,
+        mut query: Query<
+            (
+                Entity,
+                &mut OrbitCamera,
+                &mut OrbitCameraState,
+                &mut FollowTarget,
+            ),
+            Without<CameraSuspended>,
+        >,
+    ) {
+        for (entity, mut camera, mut state, mut follow) in query.iter_mut() {
+            let position = match targets.get(follow.target) {
+                Ok((_, Some(transform), None)) => transform.translation,
+                Ok((global, _, _)) => global.translation,
+                Err(_) => {
+                    match follow.on_target_lost {
+                        TargetLostResponse::Freeze => {}
+                        TargetLostResponse::Fallback(center) => camera.center = center,
+                        TargetLostResponse::Disable => camera.enabled = false,
+                    }
+                    lost.send(TargetLost {
+                        camera: entity,
+                        target: follow.target,
+                    });
+                    commands.entity(entity).remove::<FollowTarget>();
+                    continue;
+                }
+            };
+
+            let shown = follow.predict(position, &timesteps);
+            let dt = time.delta_seconds();
+            let ahead = match &mut follow.look_ahead {
+                Some(look_ahead) => look_ahead.update(shown, dt),
+                None => Vec3::ZERO,
+            };
+
+            if let Some(remaining) = follow.pending {
+                let remaining = remaining - dt;
+                if remaining > 0.0 {
+                    follow.pending = Some(remaining);
+                } else {
+                    follow.pending = None;
+                    follow.steps = None;
+                    camera.center = position + follow.offset;
+                    state.center = camera.center;
+                }
+                follow.last = Some(position);
+                continue;
+            }
+
+            let jumped = match (follow.last, follow.teleport_threshold) {
+                (Some(last), Some(threshold)) => last.distance(position) > threshold,
+                _ => false,
+            };
+            if jumped {
+                let from = camera.center;
+                let to = position + follow.offset;
+                follow.steps = None;
+                if let Some(look_ahead) = &mut follow.look_ahead {
+                    look_ahead.reset();
+                }
+                if let Some(easing) = &mut follow.pitch_easing {
+                    easing.last = None;
+                }
+                let fade = match follow.teleport_response {
+                    TeleportResponse::Snap => {
+                        camera.center = to;
+                        state.center = to;
+                        None
+                    }
+                    TeleportResponse::Fade(duration) => {
+                        follow.pending = Some(duration / 2.0);
+                        Some(duration)
+                    }
+                };
+                teleported.send(TargetTeleported {
+                    camera: entity,
+                    from,
+                    to,
+                    fade,
+                });
+            } else {
+                camera.center = shown + follow.offset + ahead;
+                if let Some(easing) = &mut follow.pitch_easing {
+                    if camera.mode == CameraMode::Orbit && camera.enabled {
+                        easing.update(&mut camera, shown, dt);
+                    }
+                }
+            }
+            follow.last = Some(position);
+        }
+    }
+
+    pub fn whiskers_system(
+        time: Res<Time>,
+        raycaster: Res<Raycaster>,
+        mut query: Query<
+            (&mut OrbitCamera, &mut FollowTarget, &CameraCollision),
+            Without<CameraSuspended>,
+        >,
+    ) {
+        let dt = time.delta_seconds();
+        for (mut camera, mut follow, collision) in query.iter_mut() {
+            if camera.mode != CameraMode::Orbit || !camera.enabled {
+                continue;
+            }
+            if let Some(whiskers) = &mut follow.whiskers {
+                whiskers.update(&mut camera, collision, &raycaster, dt);
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Fitting boxes and entities in the view.
+
+use crate::{
+    CameraCommandKind, CameraHistory, CameraMode, OrbitBounds, OrbitCamera, OrbitCameraPlugin,
+    OrbitPose, OrbitTransition, PitchDistance,
+};
+use bevy::prelude::*;
+use bevy::render::camera::{OrthographicProjection, PerspectiveProjection};
+
+/// What a [`FrameCommand`] fits in the view.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FrameTarget {
+    /// A box in world space.
+    Bounds(OrbitBounds),
+    /// Entities, by their [`OrbitBounds`] or, without any, their position.
+    Entities(Vec<Entity>),
+}
+
+/// Centers `camera` on `target` and zooms until all of it is in view, from
+/// the current angles, like "frame selected" in modeling packages. Cameras
+/// in [`CameraMode::LookAround`] and [`CameraMode::Walk`] turn towards it
+/// instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameCommand {
+    pub camera: Entity,
+    pub target: FrameTarget,
+    /// Extra room around the target, as a fraction of its size on screen.
+    pub padding: f32,
+    /// Seconds the move takes; zero jumps.
+    pub duration: f32,
+}
+
+impl FrameCommand {
+    pub fn new(camera: Entity, target: FrameTarget) -> Self {
+        FrameCommand {
+            camera,
+            target,
+            padding: 0.1,
+            duration: 0.3,
+        }
+    }
+}
+
+impl OrbitCamera {
+    /// The distance from the center of `bounds` at which all of them are in
+    /// a perspective view from the current angles.
+    pub fn fit_distance(
+        &self,
+        bounds: &OrbitBounds,
+        projection: &PerspectiveProjection,
+        padding: f32,
+    ) -> f32 {
+        let tan_y = (self.fov.unwrap_or(projection.fov) / 2.0).tan();
+        let tan_x = tan_y * projection.aspect_ratio;
+        if tan_y <= 0.0 || tan_x <= 0.0 {
+            return self.distance;
+        }
+        self.view_corners(bounds)
+            .iter()
+            .map(|corner| {
+                let corner = *corner * (1.0 + padding.max(0.0));
+                // `z` points back towards the camera.
+                let fit = (corner.x.abs() / tan_x).max(corner.y.abs() / tan_y) + corner.z;
+                fit.max(corner.z + projection.near)
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// The orthographic scale at which all of `bounds` are in view.
+    pub fn fit_scale(
+        &self,
+        bounds: &OrbitBounds,
+        projection: &OrthographicProjection,
+        padding: f32,
+    ) -> f32 {
+        let half_width = (projection.right - projection.left) / 2.0;
+        let half_height = (projection.top - projection.bottom) / 2.0;
+        if half_width <= 0.0 || half_height <= 0.0 {
+            return self.scale.unwrap_or(projection.scale);
+        }
+        self.view_corners(bounds)
+            .iter()
+            .map(|corner| {
+                let corner = *corner * (1.0 + padding.max(0.0));
+                (corner.x.abs() / half_width).max(corner.y.abs() / half_height)
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// The corners of `bounds` about their center, in view space.
+    fn view_corners(&self, bounds: &OrbitBounds) -> [Vec3; 8] {
+        let rotation = self.compute_transform().rotation.inverse();
+        let center = bounds.center();
+        let mut corners = bounds.corners();
+        for corner in corners.iter_mut() {
+            *corner = rotation * (*corner - center);
+        }
+        corners
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn frame_command_system(
+        mut commands: Commands,
+        mut events: EventReader<FrameCommand>,
+        targets: Query<(&GlobalTransform, Option<&OrbitBounds>)>,
+        mut cameras: Query<(
+            &mut OrbitCamera,
+            Option<&PerspectiveProjection>,
+            Option<&OrthographicProjection>,
+            Option<&PitchDistance>,
+            Option<&mut CameraHistory>,
+        )>,
+    ) {
+        for event in events.iter() {
+            let bounds = match &event.target {
+                FrameTarget::Bounds(bounds) => Some(*bounds),
+                FrameTarget::Entities(entities) => entities
+                    .iter()
+                    .filter_map(|entity| targets.get(*entity).ok())
+                    .map(|(transform, bounds)| match bounds {
+                        Some(bounds) => bounds.transformed(transform),
+                        None => OrbitBounds::from_point(transform.translation),
+                    })
+                    .reduce(|combined, bounds| combined.union(&bounds)),
+            };
+            let bounds = match bounds {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            let (mut camera, perspective, orthographic, crane, history) =
+                match cameras.get_mut(event.camera) {
+                    Ok(camera) => camera,
+                    Err(_) => continue,
+                };
+            if let Some(mut history) = history {
+                history.push(OrbitPose::from(&*camera));
+            }
+            match camera.mode {
+                CameraMode::Orbit | CameraMode::Map => {
+                    let mut transition =
+                        OrbitTransition::to_center(bounds.center(), event.duration);
+                    if let Some(projection) = perspective {
+                        let fit = camera.fit_distance(&bounds, projection, event.padding);
+                        // Zoomed so the distance comes out right once scaled.
+                        let scale = crane.map_or(1.0, |crane| crane.scale(camera.y));
+                        transition.distance = Some(if scale > 0.0 { fit / scale } else { fit });
+                    } else if let Some(projection) = orthographic {
+                        transition.scale =
+                            Some(camera.fit_scale(&bounds, projection, event.padding));
+                    }
+                    commands.entity(event.camera).insert(transition);
+                }
+                CameraMode::LookAround | CameraMode::Walk => {
+                    CameraCommandKind::LookAt(bounds.center()).apply(&mut camera);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Gamepad controls, alongside the mouse and touch.
+
+#[cfg(feature = "gamepad")]
+use crate::split_screen::{receives_input, CameraInput};
+use crate::OrbitCameraPlugin;
+#[cfg(feature = "gamepad")]
+use crate::{
+    ActiveOrbitCamera, CameraEvents, InputRecorder, OrbitCamera, RoutedCameraEvents,
+    PAN_PIXEL_RATIO,
+};
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::prelude::*;
+#[cfg(feature = "gamepad")]
+use bevy::render::camera::Camera;
+
+/// How fast gamepads move cameras, with the controls each camera reads set by
+/// its [`GamepadBindings`]. Gamepad input adds to the mouse and touch rather
+/// than replacing them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitGamepadSettings {
+    pub enabled: bool,
+    /// Radians per second at full tilt.
+    pub rotate_speed: f32,
+    /// World units per second at full tilt, before `pan_sensitivity` and
+    /// `distance_scaled_pan`.
+    pub pan_speed: f32,
+    /// Wheel lines per second with a trigger fully pressed.
+    pub zoom_speed: f32,
+}
+
+impl Default for OrbitGamepadSettings {
+    fn default() -> Self {
+        OrbitGamepadSettings {
+            enabled: true,
+            rotate_speed: 2.5,
+            pan_speed: 4.0,
+            zoom_speed: 4.0,
+        }
+    }
+}
+
+/// Which gamepad controls move an [`OrbitCamera`](crate::OrbitCamera). By
+/// default the right stick rotates, the left stick and the d-pad pan and the
+/// triggers zoom.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadBindings {
+    /// The only gamepad that moves the camera, for giving each player of a
+    /// split-screen game their own. Every gamepad does when unset.
+    pub gamepad: Option<Gamepad>,
+    pub rotate_x: Option<GamepadAxisType>,
+    pub rotate_y: Option<GamepadAxisType>,
+    pub pan_x: Option<GamepadAxisType>,
+    pub pan_y: Option<GamepadAxisType>,
+    pub pan_left: Option<GamepadButtonType>,
+    pub pan_right: Option<GamepadButtonType>,
+    pub pan_up: Option<GamepadButtonType>,
+    pub pan_down: Option<GamepadButtonType>,
+    pub zoom_in: Option<GamepadButtonType>,
+    pub zoom_out: Option<GamepadButtonType>,
+    /// How far, from zero to one, a stick must tilt before it moves the
+    /// camera. Tilting further ramps up from zero.
+    pub dead_zone: f32,
+}
+
+impl GamepadBindings {
+    /// Applies the dead zone to a stick reading, stretching what's left over
+    /// the full range.
+    pub fn stick(&self, x: f32, y: f32) -> Vec2 {
+        let stick = Vec2::new(x, y);
+        let length = stick.length().min(1.0);
+        if length <= self.dead_zone || self.dead_zone >= 1.0 {
+            return Vec2::ZERO;
+        }
+        stick.normalize() * (length - self.dead_zone) / (1.0 - self.dead_zone)
+    }
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        GamepadBindings {
+            gamepad: None,
+            rotate_x: Some(GamepadAxisType::RightStickX),
+            rotate_y: Some(GamepadAxisType::RightStickY),
+            pan_x: Some(GamepadAxisType::LeftStickX),
+            pan_y: Some(GamepadAxisType::LeftStickY),
+            pan_left: Some(GamepadButtonType::DPadLeft),
+            pan_right: Some(GamepadButtonType::DPadRight),
+            pan_up: Some(GamepadButtonType::DPadUp),
+            pan_down: Some(GamepadButtonType::DPadDown),
+            zoom_in: Some(GamepadButtonType::RightTrigger2),
+            zoom_out: Some(GamepadButtonType::LeftTrigger2),
+            dead_zone: 0.1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    Mouse,
+    Keyboard,
+    Touch,
+    Gamepad(Gamepad),
+}
+
+/// The device the user touched last, for showing matching prompts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LastInputDevice {
+    pub device: Option<InputDevice>,
+}
+
+impl LastInputDevice {
+    fn set(last: &mut ResMut<LastInputDevice>, device: InputDevice) {
+        if last.device != Some(device) {
+            last.device = Some(device);
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[cfg(feature = "gamepad")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn emit_gamepad_events(
+        mut events: EventWriter<CameraEvents>,
+        mut routed: EventWriter<RoutedCameraEvents>,
+        mut gamepad_events: EventReader<GamepadEvent>,
+        mut gamepads: Local<Vec<Gamepad>>,
+        mut last: ResMut<LastInputDevice>,
+        time: Res<Time>,
+        settings: Res<OrbitGamepadSettings>,
+        recorder: Res<InputRecorder>,
+        axes: Res<Axis<GamepadAxis>>,
+        button_axes: Res<Axis<GamepadButton>>,
+        button_input: Res<Input<GamepadButton>>,
+        windows: Res<Windows>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        query: Query<(Entity, &OrbitCamera, Option<&Camera>)>,
+    ) {
+        for GamepadEvent(gamepad, event) in gamepad_events.iter() {
+            match event {
+                GamepadEventType::Connected => gamepads.push(*gamepad),
+                GamepadEventType::Disconnected => gamepads.retain(|g| g != gamepad),
+                _ => {}
+            }
+        }
+        if !settings.enabled || recorder.is_replaying() {
+            return;
+        }
+
+        let dt = time.delta_seconds();
+        let mut input = CameraInput::default();
+        for (entity, camera, render_camera) in query.iter() {
+            let bindings = &camera.gamepad;
+            let alone = bindings.gamepad.is_some() || active.is_some();
+            let sent = input.camera(entity, camera.input_area, alone);
+            // Gamepads bound to a camera drive it whichever is active.
+            let receives = bindings.gamepad.is_some() || receives_input(active.as_deref(), entity);
+            if !camera.enabled || !receives {
+                continue;
+            }
+            let mut rotate = Vec2::ZERO;
+            let mut pan = Vec2::ZERO;
+            let mut zoom = 0.0;
+            let bound = gamepads
+                .iter()
+                .filter(|gamepad| bindings.gamepad.map_or(true, |bound| bound == **gamepad));
+            for gamepad in bound {
+                let axis = |axis: Option<GamepadAxisType>| {
+                    axis.and_then(|axis| axes.get(GamepadAxis(*gamepad, axis)))
+                        .unwrap_or(0.0)
+                };
+                let analog = |button: Option<GamepadButtonType>| {
+                    button
+                        .and_then(|button| button_axes.get(GamepadButton(*gamepad, button)))
+                        .unwrap_or(0.0)
+                };
+                let pressed = |button: Option<GamepadButtonType>| {
+                    let pressed = button.map_or(false, |button| {
+                        button_input.pressed(GamepadButton(*gamepad, button))
+                    });
+                    if pressed {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                };
+                let stick_rotate = bindings.stick(axis(bindings.rotate_x), axis(bindings.rotate_y));
+                let stick_pan = bindings.stick(axis(bindings.pan_x), axis(bindings.pan_y))
+                    + Vec2::new(
+                        pressed(bindings.pan_right) - pressed(bindings.pan_left),
+                        pressed(bindings.pan_up) - pressed(bindings.pan_down),
+                    );
+                let trigger_zoom = analog(bindings.zoom_in) - analog(bindings.zoom_out);
+                if stick_rotate != Vec2::ZERO || stick_pan != Vec2::ZERO || trigger_zoom != 0.0 {
+                    LastInputDevice::set(&mut last, InputDevice::Gamepad(*gamepad));
+                }
+                rotate += stick_rotate;
+                pan += stick_pan;
+                zoom += trigger_zoom;
+            }
+
+            // Converted into the pixel units of mouse events, turning towards
+            // the stick and moving the center where it points.
+            let width = render_camera
+                .and_then(|render_camera| windows.get(render_camera.window))
+                .map(|window| window.width());
+            let rotation_per_pixel = camera.rotation_per_pixel(width);
+            if rotate != Vec2::ZERO && rotation_per_pixel != 0.0 {
+                let pixels = -rotate * settings.rotate_speed * dt / rotation_per_pixel;
+                sent.push(CameraEvents::Orbit(pixels));
+            }
+            let pan_per_pixel = camera.pan_sensitivity * PAN_PIXEL_RATIO;
+            if pan != Vec2::ZERO && pan_per_pixel != 0.0 {
+                let pixels = Vec2::new(-pan.x, pan.y) * settings.pan_speed * dt / pan_per_pixel;
+                sent.push(CameraEvents::Pan(pixels));
+            }
+            if zoom != 0.0 {
+                sent.push(CameraEvents::Zoom(zoom * settings.zoom_speed * dt));
+            }
+        }
+        input.send(&mut events, &mut routed);
+    }
+
+    pub fn last_input_device_system(
+        mut last: ResMut<LastInputDevice>,
+        mut mouse_motion_events: EventReader<MouseMotion>,
+        mut mouse_wheel_events: EventReader<MouseWheel>,
+        mouse_button_input: Res<Input<MouseButton>>,
+        keyboard_input: Res<Input<KeyCode>>,
+        touches: Res<Touches>,
+    ) {
+        if touches.iter_just_pressed().next().is_some() {
+            LastInputDevice::set(&mut last, InputDevice::Touch);
+        } else if mouse_motion_events.iter().count() > 0
+            || mouse_wheel_events.iter().count() > 0
+            || mouse_button_input.get_just_pressed().next().is_some()
+        {
+            LastInputDevice::set(&mut last, InputDevice::Mouse);
+        } else if keyboard_input.get_just_pressed().next().is_some() {
+            LastInputDevice::set(&mut last, InputDevice::Keyboard);
+        }
+    }
+}
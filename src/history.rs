@@ -0,0 +1,127 @@
+//! Undo and redo for camera moves.
+
+use crate::{OrbitCamera, OrbitCameraPlugin, OrbitPose};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Poses to return to, for the camera on the same entity. Focusing pushes the
+/// pose it leaves, and [`CameraHistoryCommand`]s step back and forth.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraHistory {
+    undo: VecDeque<OrbitPose>,
+    redo: Vec<OrbitPose>,
+    capacity: usize,
+    /// How long, in seconds, undoing and redoing take to move the camera.
+    pub transition_duration: f32,
+}
+
+impl CameraHistory {
+    pub fn new(capacity: usize) -> Self {
+        CameraHistory {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            capacity,
+            transition_duration: 0.3,
+        }
+    }
+
+    /// Records a pose to come back to, dropping the oldest past the capacity
+    /// and forgetting anything that could be redone.
+    pub fn push(&mut self, pose: OrbitPose) {
+        if self.undo.back() == Some(&pose) {
+            return;
+        }
+        self.undo.push_back(pose);
+        self.redo.clear();
+        self.trim();
+    }
+
+    /// Steps back from `current`, returning the pose to move to.
+    pub fn undo(&mut self, current: OrbitPose) -> Option<OrbitPose> {
+        let pose = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(pose)
+    }
+
+    /// Steps forward from `current`, returning the pose to move to.
+    pub fn redo(&mut self, current: OrbitPose) -> Option<OrbitPose> {
+        let pose = self.redo.pop()?;
+        self.undo.push_back(current);
+        self.trim();
+        Some(pose)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.undo.len() > self.capacity {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Moves the remembered positions along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        for pose in self.undo.iter_mut().chain(self.redo.iter_mut()) {
+            pose.center += offset;
+        }
+    }
+}
+
+impl Default for CameraHistory {
+    fn default() -> Self {
+        CameraHistory::new(32)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CameraHistoryCommand {
+    Undo(Entity),
+    Redo(Entity),
+}
+
+impl OrbitCameraPlugin {
+    pub fn history_system(
+        mut commands: Commands,
+        mut events: EventReader<CameraHistoryCommand>,
+        mut query: Query<(&OrbitCamera, &mut CameraHistory)>,
+    ) {
+        for event in events.iter() {
+            let (entity, undo) = match *event {
+                CameraHistoryCommand::Undo(entity) => (entity, true),
+                CameraHistoryCommand::Redo(entity) => (entity, false),
+            };
+            if let Ok((camera, mut history)) = query.get_mut(entity) {
+                let current = OrbitPose::from(camera);
+                let pose = if undo {
+                    history.undo(current)
+                } else {
+                    history.redo(current)
+                };
+                if let Some(pose) = pose {
+                    let duration = history.transition_duration;
+                    commands.entity(entity).insert(pose.transition(duration));
+                }
+            }
+        }
+    }
+}
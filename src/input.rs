@@ -0,0 +1,37 @@
+//! Choosing which of the plugin's own input systems run.
+
+/// Which built-in devices drive cameras through
+/// [`CameraEvents`](crate::CameraEvents). Insert it before adding the
+/// [`OrbitCameraPlugin`](crate::OrbitCameraPlugin), which reads it once to
+/// schedule its systems, and turn a device off to send the events from your
+/// own input layer instead, such as networked input or custom widgets, while
+/// the plugin still moves the cameras.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BuiltInInput {
+    /// Dragging, the wheel and its zoom keys, and double-clicking to focus.
+    pub mouse: bool,
+    /// Touch gestures, including long presses to focus, with the `touch`
+    /// feature.
+    pub touch: bool,
+    /// Gamepad sticks, with the `gamepad` feature.
+    pub gamepad: bool,
+}
+
+impl BuiltInInput {
+    /// No built-in input at all.
+    pub const NONE: BuiltInInput = BuiltInInput {
+        mouse: false,
+        touch: false,
+        gamepad: false,
+    };
+}
+
+impl Default for BuiltInInput {
+    fn default() -> Self {
+        BuiltInInput {
+            mouse: true,
+            touch: true,
+            gamepad: true,
+        }
+    }
+}
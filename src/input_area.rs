@@ -0,0 +1,62 @@
+//! Keeping each window's input to its own cameras.
+
+use crate::exclusions::cursor_position;
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// Where an [`OrbitCamera`](crate::OrbitCamera) takes the built-in mouse,
+/// keyboard and touch input from, for editors with a camera per window or per
+/// view. Cameras with a scoped area only take
+/// [`RoutedCameraEvents`](crate::RoutedCameraEvents) sent to them, and ignore
+/// [`CameraEvents`](crate::CameraEvents) sent to every camera, such as from
+/// game code or a replayed [`InputRecording`](crate::InputRecording).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputArea {
+    /// Input from any window.
+    #[default]
+    Everywhere,
+    /// The mouse over the camera's window, and the keyboard while it has
+    /// focus.
+    Window,
+    /// The mouse and touches over a rectangle of the camera's window, given
+    /// by its bottom-left and top-right corners in the same space as
+    /// `Window::cursor_position`, such as one of the views of
+    /// [`split_screen_grid`](crate::split_screen_grid), and the keyboard while
+    /// the window has focus and the cursor is over the rectangle.
+    Rect { min: Vec2, max: Vec2 },
+}
+
+impl InputArea {
+    pub fn is_scoped(&self) -> bool {
+        *self != InputArea::Everywhere
+    }
+
+    /// Whether `position`, in the camera's window, is in the area.
+    pub fn contains(&self, position: Vec2) -> bool {
+        match self {
+            InputArea::Rect { min, max } => {
+                position.cmpge(min.min(*max)).all() && position.cmplt(min.max(*max)).all()
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether the cursor is over the area of `camera`.
+    pub(crate) fn has_cursor(&self, windows: &Windows, camera: Option<&Camera>) -> bool {
+        !self.is_scoped()
+            || cursor_position(windows, camera).map_or(false, |position| self.contains(position))
+    }
+
+    /// Whether key presses reach `camera`.
+    pub(crate) fn has_keyboard(&self, windows: &Windows, camera: Option<&Camera>) -> bool {
+        let focused = camera
+            .and_then(|camera| windows.get(camera.window))
+            .map_or(false, |window| window.is_focused());
+        match self {
+            InputArea::Everywhere => true,
+            InputArea::Window => focused,
+            InputArea::Rect { .. } => focused && self.has_cursor(windows, camera),
+        }
+    }
+}
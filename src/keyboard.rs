@@ -0,0 +1,120 @@
+//! Moving cameras continuously from the keyboard.
+
+use crate::split_screen::{receives_input, CameraInput};
+use crate::{
+    ActiveOrbitCamera, CameraEvents, InputCapture, InputRecorder, OrbitCamera, OrbitCameraPlugin,
+    RoutedCameraEvents, PAN_PIXEL_RATIO,
+};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// Lets the keyboard rotate, pan and zoom the camera on the same entity for
+/// as long as keys are held. Any of the keys bound to an action triggers it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardControls {
+    pub orbit_left: Vec<KeyCode>,
+    pub orbit_right: Vec<KeyCode>,
+    pub orbit_up: Vec<KeyCode>,
+    pub orbit_down: Vec<KeyCode>,
+    pub pan_left: Vec<KeyCode>,
+    pub pan_right: Vec<KeyCode>,
+    pub pan_up: Vec<KeyCode>,
+    pub pan_down: Vec<KeyCode>,
+    pub zoom_in: Vec<KeyCode>,
+    pub zoom_out: Vec<KeyCode>,
+    /// Radians per second.
+    pub rotate_speed: f32,
+    /// World units per second, before `pan_sensitivity` and
+    /// `distance_scaled_pan`.
+    pub pan_speed: f32,
+    /// Wheel lines per second.
+    pub zoom_speed: f32,
+}
+
+impl Default for KeyboardControls {
+    fn default() -> Self {
+        KeyboardControls {
+            orbit_left: vec![KeyCode::Left],
+            orbit_right: vec![KeyCode::Right],
+            orbit_up: vec![KeyCode::Up],
+            orbit_down: vec![KeyCode::Down],
+            pan_left: vec![KeyCode::A],
+            pan_right: vec![KeyCode::D],
+            pan_up: vec![KeyCode::W],
+            pan_down: vec![KeyCode::S],
+            zoom_in: vec![KeyCode::Equals, KeyCode::NumpadAdd, KeyCode::PageUp],
+            zoom_out: vec![KeyCode::Minus, KeyCode::NumpadSubtract, KeyCode::PageDown],
+            rotate_speed: 1.5,
+            pan_speed: 4.0,
+            zoom_speed: 4.0,
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::too_many_arguments)]
+    pub fn emit_keyboard_events(
+        mut events: EventWriter<CameraEvents>,
+        mut routed: EventWriter<RoutedCameraEvents>,
+        time: Res<Time>,
+        keyboard_input: Res<Input<KeyCode>>,
+        recorder: Res<InputRecorder>,
+        capture: Res<InputCapture>,
+        windows: Res<Windows>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        query: Query<(Entity, &OrbitCamera, &KeyboardControls, Option<&Camera>)>,
+    ) {
+        if recorder.is_replaying() || capture.keyboard {
+            return;
+        }
+        let held = |keys: &[KeyCode]| {
+            if keys.iter().any(|key| keyboard_input.pressed(*key)) {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        let dt = time.delta_seconds();
+        let mut input = CameraInput::default();
+        for (entity, camera, controls, render_camera) in query.iter() {
+            // Cameras without controls aren't seen here, so every camera is
+            // sent its own events.
+            let sent = input.camera(entity, camera.input_area, true);
+            let area = camera.input_area;
+            if !camera.enabled
+                || !area.has_keyboard(&windows, render_camera)
+                || !receives_input(active.as_deref(), entity)
+            {
+                continue;
+            }
+            let rotate = Vec2::new(
+                held(&controls.orbit_right) - held(&controls.orbit_left),
+                held(&controls.orbit_down) - held(&controls.orbit_up),
+            );
+            let pan = Vec2::new(
+                held(&controls.pan_right) - held(&controls.pan_left),
+                held(&controls.pan_up) - held(&controls.pan_down),
+            );
+            let zoom = held(&controls.zoom_in) - held(&controls.zoom_out);
+
+            // Converted into the pixel units of mouse events, like gamepads.
+            let width = render_camera
+                .and_then(|render_camera| windows.get(render_camera.window))
+                .map(|window| window.width());
+            let rotation_per_pixel = camera.rotation_per_pixel(width);
+            if rotate != Vec2::ZERO && rotation_per_pixel != 0.0 {
+                let pixels = -rotate * controls.rotate_speed * dt / rotation_per_pixel;
+                sent.push(CameraEvents::Orbit(pixels));
+            }
+            let pan_per_pixel = camera.pan_sensitivity * PAN_PIXEL_RATIO;
+            if pan != Vec2::ZERO && pan_per_pixel != 0.0 {
+                let pixels = Vec2::new(-pan.x, pan.y) * controls.pan_speed * dt / pan_per_pixel;
+                sent.push(CameraEvents::Pan(pixels));
+            }
+            if zoom != 0.0 {
+                sent.push(CameraEvents::Zoom(zoom * controls.zoom_speed * dt));
+            }
+        }
+        input.send(&mut events, &mut routed);
+    }
+}
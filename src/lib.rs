@@ -1,7 +1,20 @@
 //! An orbit controls plugin for bevy.
 //!
 //! To control the camera, drag the mouse. The left button rotates. The wheel
-//! zooms.
+//! zooms, ten times faster while Shift is held. On touch screens, one finger
+//! rotates, and two fingers pan, pinch to zoom and twist to rotate, all within
+//! the same gesture, with their own `touch_*_sensitivity`. A long-press moves
+//! the orbit center to the point under the finger, as does double-clicking with
+//! the mouse. Gamepads work alongside the mouse: the right stick rotates, the
+//! left stick or d-pad pans and the triggers zoom, as set by
+//! [`GamepadBindings`], and [`LastInputDevice`] tracks which device was used
+//! last. Cameras with [`KeyboardControls`] rotate, pan and zoom while keys are
+//! held, cameras with [`ContinuousZoom`] zoom smoothly while a key or button is
+//! held, cameras with [`KeyboardSteps`] turn by exact increments on the arrow
+//! keys, cameras with [`FlickTurn`] turn around on a quick flick, and cameras
+//! with [`TiltParallax`] turn a little as the device tilts, from
+//! [`DeviceOrientation`] readings that the `device_orientation` feature takes
+//! from the browser.
 //!
 //! ## Usage
 //!
@@ -20,59 +33,897 @@
 //!     .insert(OrbitCamera::default());
 //! ```
 //!
+//! To change how cameras behave with the app's state, add a
+//! [`StateOverridesPlugin`] for the state type and give cameras a
+//! [`StateOverrides`] table, such as lowering the sensitivity during dialogue.
+//! The overrides of a [`CameraZone`] the camera is in apply on top, and leaving
+//! the zone or the state lifts only its own.
+//!
+//! Apps with a single camera can insert the [`PrimaryOrbitCamera`] resource to
+//! read its pose and set its target without a query.
+//!
+//! For split-screen games, bind each camera to its player's gamepad with
+//! `gamepad.gamepad`, so that gamepad moves only that camera, through
+//! [`RoutedCameraEvents`]. [`split_screen_grid`] lays the players' views out in
+//! a grid, and [`spawn_split_screen`] spawns a camera for each view, scoped to
+//! it and bound to its player's gamepad. Bevy 0.5 draws every camera over the
+//! whole window, so rendering into the views is up to the app's render graph.
+//!
+//! Editors with a camera per window or per view set each camera's `input_area`
+//! to [`InputArea::Window`] or [`InputArea::Rect`], so the mouse and keyboard
+//! only move the camera they're over, through [`RoutedCameraEvents`]. Drags
+//! stay with the camera they started over.
+//!
+//! Where views overlap, such as a picture-in-picture, insert an
+//! [`ActiveOrbitCamera`] to send the built-in input to one camera only. Switch
+//! it with [`ActiveOrbitCamera::set`], or let
+//! [`ActiveOrbitCamera::following_cursor`] pick the view under the cursor as
+//! each drag starts.
+//!
+//! Game code can also drive any camera by sending [`OrbitCameraCommand`]
+//! events, which set its center, distance or angles, or turn it towards a
+//! point, and move it there with its usual smoothing and limits.
+//!
+//! A [`FrameCommand`] frames a box or a set of entities, moving the center to
+//! them and zooming until they fit the perspective or orthographic view from
+//! the current angles, as "frame selected" does in modeling packages.
+//! [`OrbitCamera::fit_distance`] and [`OrbitCamera::fit_scale`] give the zoom
+//! without moving the camera.
+//!
+//! Games that rebase their world origin for precision send
+//! [`WorldOriginShifted`] with the offset the world moved by, and the plugin
+//! moves its cameras' centers, smoothed state, transitions, follow state and
+//! history along with it, so nothing jumps. Saved bookmarks and camera paths
+//! are left as they are.
+//!
+//! Dragging grabs the world, so the scene follows the cursor. Set the
+//! [`DragSemantics`] resource, or a camera's `drag_semantics`, to
+//! `MoveCamera` to move the camera with the cursor instead, for both rotating
+//! and panning.
+//!
+//! Set `distance_scaled_pan` to pan further the further out the camera is,
+//! so panning feels the same at any zoom.
+//!
+//! `invert_pan_x` and `invert_pan_y` reverse panning, and `invert_zoom`
+//! reverses the wheel. Left unset, `invert_zoom` follows the
+//! [`ScrollDirection`] resource, reversing the wheel under natural scrolling;
+//! the `natural_scrolling` feature detects it on macOS and in Safari.
+//!
+//! A camera's `rotate_binding`, `pan_binding` and `zoom_binding` are
+//! [`MouseBinding`]s, which can require [`Modifiers`] with the button for
+//! Maya-style chords, such as orbiting with Alt and the left button and zooming
+//! with Alt and the right. Dragging with `zoom_binding` zooms; it's unset by
+//! default.
+//!
+//! Set a camera's `control_scheme` to [`ControlScheme::ScrollToRotate`] for
+//! mice that are awkward to drag: the wheel turns the camera, pitching while
+//! Shift is held, and dragging up and down or pressing + and - zooms.
+//!
+//! ## Filtering input
+//!
+//! Mouse and touch input reaches cameras as [`CameraEvents`]. An
+//! [`InputFilter`] on a camera can drop or rewrite them, for example to block
+//! zooming during a cutscene or to quantize rotation.
+//!
+//! Cameras only rotate, pan and zoom in response to these events, so any input
+//! layer can drive them by sending its own, or [`RoutedCameraEvents`] for a
+//! single camera. Insert a [`BuiltInInput`] before adding the plugin to turn
+//! the built-in mouse, touch or gamepad input off, such as to feed networked
+//! input or custom widgets instead.
+//!
+//! [`InputExclusions`] on a camera mark rectangles of its window, such as a
+//! toolbar drawn by custom UI, where it ignores the mouse and touch.
+//!
+//! Bevy UI is handled for you: while the cursor is over a node with an
+//! `Interaction`, such as a button, cameras ignore the mouse, so clicking it
+//! doesn't also rotate the scene. Give a panel an `Interaction` too for it to
+//! block the cameras.
+//!
+//! Immediate mode UI such as egui covers no fixed rectangle, so instead set the
+//! [`InputCapture`] resource each frame from whether the UI wants the pointer
+//! or the keyboard; while it does, every camera ignores that input. The plugin
+//! keeps the Bevy UI hover in its own [`UiPointerCapture`] and never writes to
+//! the app's flags.
+//!
+//! An [`InputRecorder`] captures these events with their timestamps as an
+//! [`InputRecording`], which can be saved, attached to a bug report and
+//! replayed in place of live input.
+//!
+//! A [`CommandLog`] on a camera goes further and logs every change to its
+//! target as an [`OrbitCommand`] with a timestamp, whatever made it. Pushing
+//! commands to the log applies them, and the entries can be undone, replayed
+//! or sent to other clients to keep their cameras in sync.
+//!
+//! ## Smoothing
+//!
+//! The fields of [`OrbitCamera`] are the target the camera moves towards. The
+//! pose actually applied to the transform lives in [`OrbitCameraState`], which
+//! the plugin inserts and eases towards the target with `1 - exp(-dt / tau)`,
+//! so the motion is the same at any frame rate. The time constants are set per
+//! channel through [`Smoothing`]; a `tau` of zero snaps instantly, which is the
+//! default. [`Smoothing::from_damping_factor`] matches the feel of a three.js
+//! `dampingFactor`.
+//!
+//! Each frame, input is applied during [`OrbitCameraSystem::Update`]: rotating
+//! and panning in the order they came in, then zooming. The limits clamp the
+//! target during [`OrbitCameraSystem::Limits`], in the order listed on
+//! [`LimitOrder`], before it's smoothed, so the view eases into them. Insert
+//! an [`ApplicationOrder`] before adding the plugin to rotate before panning or
+//! the other way round, zoom first, or clamp to the ranges after smoothing so
+//! the view springs back from them instead.
+//!
+//! Inserting an [`OrbitTransition`], such as one from
+//! [`OrbitCamera::animate_to`], moves the target to a new pose over time with a
+//! chosen [`Easing`], for focusing an editor on an object. Input is ignored
+//! while it runs, unless its `input` is [`TransitionInput::Blended`].
+//! A transition inserted while another runs takes over from wherever that one
+//! got to. Set its `interruption` to [`Interruption::Queue`] to wait for the
+//! running one instead, or to [`Interruption::Blend`] to take over at the speed
+//! the camera was moving, so scripted moves chain without a pause.
+//!
+//! Effects such as screen shake and recoil go in a [`CameraOffset`] on the
+//! camera, which is added in camera space on top of the controlled transform
+//! each time it's written. [`OrbitCamera::controlled_transform`] gives the
+//! transform without it.
+//!
+//! A [`CameraBlendStack`] mixes the poses that several behaviors want, such as
+//! a tour, a scripted shot and the user's own control, so they compose instead
+//! of fighting. Each named [`BlendLayer`] has a priority, a weight and fade
+//! times, and covers the layers below it and the camera's smoothed pose;
+//! releasing a layer fades it out and hands the view back.
+//!
+//! While a camera's window is minimized, the camera is marked
+//! [`CameraSuspended`] and its smoothing and animations wait, resuming with a
+//! [`CameraSuspendEvent`] when the window is restored. [`SuspendSettings`] can
+//! also suspend cameras when their window loses focus.
+//!
+//! ## Modes
+//!
+//! [`CameraMode::Orbit`] circles the camera around `center`, or with
+//! [`RotationPivot::Cursor`] around the point under the cursor where a drag
+//! started. On an orthographic camera, zooming scales the projection within
+//! `scale_range` instead, since moving it closer changes nothing.
+//! [`CameraMode::LookAround`] places the camera at `center` and turns it on the
+//! spot, for viewing panoramas and skyboxes: dragging moves the view with the
+//! cursor and zooming narrows the field of view within `fov_range` instead of
+//! changing `distance`. Set `adapt_rotation_to_fov` to slow rotation down as
+//! the view narrows.
+//! [`CameraMode::Map`] looks straight down on `center` from `distance` above,
+//! for orthographic floor plans and maps: pitch is ignored, and zooming scales
+//! the projection within `scale_range` about the point under the cursor. Set
+//! `rotation_locked` to keep the map from turning.
+//! [`CameraMode::Walk`] looks around from `center` like `LookAround`, but keeps
+//! `center` at eye level above the [`WalkSurface`] of a [`WalkConstraint`],
+//! for walkthroughs: scrolling walks forward and panning slides along the
+//! ground.
+//! A [`CameraRail`] keeps the eye of an orbiting camera on a spline, at the
+//! point closest to the center, at a point set by game code or at the center's
+//! progress between two points, while it keeps looking at the center, for
+//! scripted sections that still get smoothing and collision.
+//!
+//! A [`FollowTarget`] keeps the orbit center on a moving entity, such as the
+//! player in a third-person game, while the angles and distance stay under the
+//! user's control. Move the entity before [`OrbitCameraSystem::Update`] to keep
+//! the camera from trailing a frame behind. When the entity moves further than
+//! `teleport_threshold` in a frame, the camera snaps there instead of sweeping
+//! across, or jumps halfway through a fade that the app draws on
+//! [`TargetTeleported`]. Its `on_target_lost` picks whether the camera freezes,
+//! returns to a fallback center or turns off once the entity is despawned.
+//! Give it a [`TargetPrediction`] for targets moved in a `FixedTimestep`, such
+//! as by physics, to interpolate or extrapolate them to the time of the frame
+//! so the camera doesn't judder.
+//! A [`LookAhead`] shifts the center ahead of the target as it moves, or in the
+//! direction the player steers, so players see more of where they're going.
+//! [`PitchEasing`] tilts the camera as the target jumps or flies, to keep it
+//! framed, and backs off while the user pitches by hand.
+//! With a [`CameraCollision`] on the camera too, [`Whiskers`] probe either side
+//! of the eye and yaw it gently away from nearby walls before they block the
+//! view, for following down corridors.
+//! While the target is inside a [`CameraZone`], the camera blends to the zone's
+//! fixed yaw, pitch range or distance, and back when it leaves, like the camera
+//! volumes of level editors; the zone with the highest `priority` wins where
+//! they overlap.
+//!
+//! A [`Trackball`] lets an orbiting camera tumble freely over the poles,
+//! rolling as it goes; its `auto_horizon` gradually levels the horizon again.
+//! Set `rotation_mode` to [`RotationMode::Trackball`] to give a camera one, for
+//! molecules and point clouds with no natural up.
+//!
+//! A [`DollyZoom`] changes the field of view while moving the camera to keep
+//! the subject the same size on screen.
+//!
+//! Adding a [`ProjectionSwitch`] to a perspective camera lets it animate to
+//! and from a matching orthographic projection. [`ViewportMargins`] keep the
+//! orbit center in the middle of the part of the window not covered by UI,
+//! and `resize_anchor` picks whether resizing the window keeps the visible
+//! height, the visible width or the pixel size of the scene.
+//!
+//! `pitch_range` and `yaw_range` limit the angles, for example to keep the
+//! camera above the ground or within an arc, even when `x` and `y` are set
+//! directly.
+//! `distance_range` keeps the camera from zooming through the center or out
+//! too far, even when `distance` is set directly.
+//! A [`FrustumDistanceLimit`] stops the camera from zooming in so far that
+//! the [`OrbitBounds`] of an entity no longer fit in the view.
+//! Marking the entities of a level with [`SceneBoundsMember`] and giving the
+//! camera a [`SceneBoundary`] keeps it from being panned or zoomed out far
+//! outside of them.
+//! [`OrbitCamera::ensure_visible`] animates the smallest zoom out, or move of
+//! the center, that brings a set of points into view, for showing where
+//! something happened.
+//! A [`DistancePitchLimit`] narrows how low the camera may look from as it
+//! zooms out, such as keeping it above the horizon far from terrain.
+//! The other way round, a [`PitchDistance`] scales the distance by the pitch,
+//! swinging in close when the view is level and out when it looks down, to keep
+//! characters readable from every angle in RPGs.
+//! A [`CameraCollision`] slides the orbit center along the surfaces found by
+//! the [`Raycaster`] while panning, so it never ends up inside walls.
+//! Its spring arm pulls an orbiting camera in towards the center when geometry
+//! comes between them, and lets it back out to `distance` once clear, keeping
+//! the target in view in third-person games.
+//!
+//! ## Angles
+//!
+//! `x` turns the camera around the Y axis and `y` is its angle down from
+//! straight above. Set `up` to orbit about another axis, such as `Vec3::Z` for
+//! content from Blender or CAD tools. [`OrbitCamera::angles`] and
+//! [`OrbitCamera::set_angles`] convert from and to other [`Conventions`], such
+//! as yaw from +X and pitch above the horizon in degrees, for exchanging views
+//! with other tools.
+//! [`OrbitCamera::set_exact`] takes a pose typed into numeric fields, in
+//! degrees, validates it and applies or animates it.
+//! [`OrbitCamera::compute_transform`] and [`OrbitCamera::would_clamp`] preview
+//! where a camera will end up and which limits a pose would hit, without
+//! changing it.
+//!
+//! [`OrbitCamera::view_preset`] and [`OrbitCamera::view_preset_transition`]
+//! turn a camera to a [`ViewPreset`] such as the front, top or isometric view,
+//! keeping its distance and center, and [`ViewPresetKeys`] binds them to
+//! Blender's numpad keys.
+//!
+//! ## Bookmarks
+//!
+//! [`CameraBookmarks`] keeps named [`OrbitPose`]s for the camera on the same
+//! entity. Restore one by inserting `bookmark.pose.transition(duration)`.
+//! Bookmarks don't capture thumbnails, since Bevy 0.5 has no render-to-texture
+//! in its core renderer; galleries of saved views render their own previews
+//! from each pose.
+//! [`OrbitPose::to_pose_string`] and [`OrbitPose::from_pose_string`] turn a
+//! pose into short text for sharing viewpoints in bug reports and URLs.
+//!
+//! Cameras with a [`CameraHistory`] remember the pose they had before each
+//! focus, and step back and forth through them on [`CameraHistoryCommand`]s.
+//!
+//! In web builds with the `url_hash` feature, a camera with a `UrlHashPose`
+//! keeps its pose in the page's URL hash, so the address links to the view.
+//!
+//! A [`CameraPath`] asset animates the camera through keyframed poses when
+//! played by a [`CameraPathPlayer`], which can also seek anywhere in the path
+//! for scrubbing on a timeline. Passing a keyframe sends [`KeyframeCrossed`].
+//! The `path_io` feature exports paths as CSV, JSON or a glTF camera
+//! animation for rendering in other tools, and imports JSON paths and glTF
+//! camera animations to play as tours with [`CameraPathPlayer::tour`].
+//!
+//! Pose strings and JSON paths are versioned, and ones saved by older versions
+//! of the crate are upgraded as they load. [`Migrations`] does the same for
+//! settings an app saves itself, one version at a time.
+//!
+//! ## Picking
+//!
+//! Focusing on the point under the cursor, and zooming a map towards it, goes
+//! through the [`Raycaster`] resource. By default it hits the plane through the
+//! orbit center facing the camera's up; insert your own [`RaycastProvider`], or
+//! enable the `bevy_mod_raycast` feature and add `ModRaycastPlugin` to pick
+//! meshes. Collision casts through the same provider, which blocks nothing
+//! unless it implements [`RaycastProvider::cast`].
+//!
+//! Cameras with a [`FocusCycle`] move the orbit center from one entity in its
+//! list to the next on Tab, or back with Shift+Tab, keeping their angles, for
+//! inspecting several items in turn. Give an entity a [`Focusable`] to choose
+//! the distance and pitch it's seen from, or to frame its [`OrbitBounds`].
+//!
+//! ## Diagnostics
+//!
+//! Add [`OrbitCameraDiagnosticsPlugin`] to report the camera's distance,
+//! angular speed and input events through Bevy's `Diagnostics`. Misconfigured
+//! cameras, such as ones with inverted limits, are logged as warnings.
+//!
+//! [`CameraFeedback`] events report when a limit holds the camera back, when
+//! it turns by a fixed step and when a move is undone, for driving gamepad
+//! rumble or sounds.
+//!
+//! [`CameraActivity`] events report each frame's orbit, pan and zoom, and
+//! when a camera starts and stops moving, for updating overlays or a minimap
+//! only while the view changes.
+//!
+//! Once a camera has been still for the `idle_gap` of
+//! [`InteractionSummarySettings`], an [`InteractionSummary`] reports how long
+//! the burst of interaction lasted and how far it turned, panned and zoomed,
+//! for logging engagement in product viewers.
+//!
+//! The [`FrameBudget`] resource sets how many frames pass between refits of the
+//! scene bounds and frustum limits, and how many rays collision may cast per
+//! move, to keep the plugin cheap on low-end mobile devices.
+//!
+//! The `touch_emulation` feature adds `TouchEmulation`, which turns mouse
+//! drags with Alt, Ctrl or Shift held into one-finger, pinching or two-finger
+//! touches, for trying the touch controls on a desktop.
+//!
+//! ## Features
+//!
+//! No features are on by default, which builds just the mouse and keyboard
+//! controls. Turn on the `touch` and `gamepad` features for touch and gamepad
+//! controls.
+//!
+//! Transitions and collision are always built in. Focusing, bookmarks, view
+//! presets, cycling and history all animate through [`OrbitTransition`], and
+//! [`CameraCollision`] does nothing until the app's [`Raycaster`] casts against
+//! its geometry, pulling in no physics engine, so leaving either out would save
+//! next to nothing.
+//!
+//! The `serde` feature derives `Serialize` and `Deserialize` for
+//! [`OrbitCamera`] and its settings. [`OrbitCamera`] is registered for
+//! reflection too, so with `serde` it's saved in scenes along with everything
+//! needed to restore the view.
+//!
 //! ## Compatibility
 //!
 //! - `v2.x` – Bevy `0.5`.
 //! - `v1.x` – Bevy `0.4`.
+//!
+//! The crate builds on Rust 1.62 or newer.
 
 use bevy::input::mouse::MouseMotion;
 use bevy::input::mouse::MouseScrollUnit::{Line, Pixel};
 use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
-use bevy::render::camera::Camera;
+use bevy::render::camera::{
+    Camera, CameraProjection, OrthographicProjection, PerspectiveProjection,
+};
+use bevy::utils::HashSet;
 use std::ops::RangeInclusive;
 
+mod activity;
+mod bindings;
+mod blend;
+mod bookmarks;
+mod bounds;
+mod budget;
+mod camera_command;
+mod capture;
+mod collision;
+mod command_log;
+mod conventions;
+mod crane;
+mod cycle;
+mod diagnostics;
+mod dolly;
+mod exclusions;
+mod feedback;
+mod filter;
+mod flick;
+mod focus;
+mod focusable;
+mod follow;
+mod framing;
+mod gamepad;
+mod history;
+mod input;
+mod input_area;
+mod keyboard;
+mod migrate;
+#[cfg(feature = "bevy_mod_raycast")]
+mod mod_raycast;
+mod offset;
+mod order;
+mod origin;
+mod path;
+#[cfg(feature = "path_io")]
+mod path_io;
+mod pitch_limit;
+mod pivot;
+mod pose;
+mod presets;
+mod preview;
+mod primary;
+mod projection;
+mod rail;
+mod ray;
+mod recording;
+mod resize;
+mod scroll;
+mod split_screen;
+mod state_overrides;
+mod steps;
+mod suspend;
+mod tilt;
+mod touch;
+#[cfg(feature = "touch_emulation")]
+mod touch_emulation;
+mod trackball;
+mod transition;
+#[cfg(all(feature = "url_hash", target_arch = "wasm32"))]
+mod url_hash;
+mod validate;
+mod viewport;
+mod visible;
+mod walk;
+mod zones;
+mod zoom_hold;
+
+pub use activity::*;
+pub use bindings::*;
+pub use blend::*;
+pub use bookmarks::*;
+pub use bounds::*;
+pub use budget::*;
+pub use camera_command::*;
+pub use capture::*;
+pub use collision::*;
+pub use command_log::*;
+pub use conventions::*;
+pub use crane::*;
+pub use cycle::*;
+pub use diagnostics::*;
+pub use dolly::*;
+pub use exclusions::*;
+pub use feedback::*;
+pub use filter::*;
+pub use flick::*;
+pub use focus::*;
+pub use focusable::*;
+pub use follow::*;
+pub use framing::*;
+pub use gamepad::*;
+pub use history::*;
+pub use input::*;
+pub use input_area::*;
+pub use keyboard::*;
+pub use migrate::*;
+#[cfg(feature = "bevy_mod_raycast")]
+pub use mod_raycast::*;
+pub use offset::*;
+pub use order::*;
+pub use origin::*;
+pub use path::*;
+pub use pitch_limit::*;
+pub use pivot::*;
+pub use pose::*;
+pub use presets::*;
+pub use preview::*;
+pub use primary::*;
+pub use projection::*;
+pub use rail::*;
+pub use ray::*;
+pub use recording::*;
+pub use resize::*;
+pub use scroll::*;
+pub use split_screen::*;
+pub use state_overrides::*;
+pub use steps::*;
+pub use suspend::*;
+pub use tilt::*;
+pub use touch::*;
+#[cfg(feature = "touch_emulation")]
+pub use touch_emulation::*;
+pub use trackball::*;
+pub use transition::*;
+#[cfg(all(feature = "url_hash", target_arch = "wasm32"))]
+pub use url_hash::*;
+pub use viewport::*;
+pub use walk::*;
+pub use zones::*;
+pub use zoom_hold::*;
+
 const LINE_TO_PIXEL_RATIO: f32 = 0.1;
+// Mouse deltas used to be scaled by the frame time; these keep the old feel at
+// 60 FPS while making the result independent of the frame rate.
+const ROTATE_PIXEL_RATIO: f32 = 1.0 / 60.0;
+const PAN_PIXEL_RATIO: f32 = 1.0 / 60.0;
+const SNAP_EPSILON: f32 = 1e-4;
+// The distance at which `distance_scaled_pan` pans as much as unscaled panning.
+const PAN_REFERENCE_DISTANCE: f32 = 5.0;
+// How far one line of scrolling turns, and dragging zooms, with
+// `ControlScheme::ScrollToRotate`.
+const WHEEL_ROTATE_PIXELS: f32 = 15.0;
+const DRAG_ZOOM_RATIO: f32 = 1.0 / 50.0;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum CameraEvents {
     Orbit(Vec2),
     Pan(Vec2),
     Zoom(f32),
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CameraMode {
+    #[default]
+    Orbit,
+    LookAround,
+    Map,
+    Walk,
+}
+
+/// Whether dragging moves the scene or the camera. Set as a resource for
+/// all cameras, or per camera through [`OrbitCamera::drag_semantics`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DragSemantics {
+    /// The scene follows the cursor, as if grabbed.
+    #[default]
+    GrabWorld,
+    /// The camera follows the cursor, so the scene moves the other way.
+    MoveCamera,
+}
+
+/// Which mouse input drives which movement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlScheme {
+    /// Dragging with `rotate_binding` rotates and the wheel zooms.
+    #[default]
+    Standard,
+    /// The wheel turns the camera, pitching while Shift is held, while
+    /// dragging up and down with `rotate_binding` or pressing + and - zooms,
+    /// for mice that are awkward to drag.
+    ScrollToRotate,
+}
+
+/// Everything needed to restore the view is kept here, so saving the
+/// component in a scene, or with the `serde` feature anywhere else, is enough
+/// to bring the camera back.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(not(feature = "serde"), reflect_value(Component, PartialEq))]
+#[cfg_attr(
+    feature = "serde",
+    reflect_value(Component, PartialEq, Serialize, Deserialize)
+)]
 pub struct OrbitCamera {
+    pub mode: CameraMode,
     pub x: f32,
     pub y: f32,
     pub pitch_range: RangeInclusive<f32>,
+    /// Limits `x`, which isn't wrapped. Unlimited by default.
+    pub yaw_range: RangeInclusive<f32>,
     pub distance: f32,
+    /// Kept to even when `distance` is set directly, so the camera can't pass
+    /// through the center.
+    pub distance_range: RangeInclusive<f32>,
     pub center: Vec3,
+    /// The axis the camera orbits about, which `y` is measured from.
+    pub up: Vec3,
+    /// Vertical field of view driven by the plugin, if any. Zooming in
+    /// [`CameraMode::LookAround`] takes it over from the projection.
+    pub fov: Option<f32>,
+    pub fov_range: RangeInclusive<f32>,
+    /// Orthographic scale driven by the plugin, if any. Zooming an
+    /// orthographic camera takes it over from the projection.
+    pub scale: Option<f32>,
+    pub scale_range: RangeInclusive<f32>,
+    pub rotation_locked: bool,
+    pub rotation_pivot: RotationPivot,
+    pub rotation_mode: RotationMode,
+    /// Overrides the [`DragSemantics`] resource for this camera.
+    pub drag_semantics: Option<DragSemantics>,
+    /// Keeps the framing steady on resize, instead of Bevy's default.
+    pub resize_anchor: Option<ResizeAnchor>,
     pub rotate_sensitivity: f32,
+    /// When set, dragging across the whole width of the window turns the
+    /// camera by this many radians, times `rotate_sensitivity`, whatever the
+    /// window size.
+    pub rotation_per_width: Option<f32>,
+    /// Slows rotation as zooming narrows `fov`, so dragging moves the scene
+    /// the same distance on screen at any zoom.
+    pub adapt_rotation_to_fov: bool,
     pub pan_sensitivity: f32,
+    /// Scales panning with `distance`, or with `scale` once zooming has set
+    /// it, so dragging moves the scene about as far on screen at any zoom.
+    pub distance_scaled_pan: bool,
+    /// Reverse panning along each axis of the screen.
+    pub invert_pan_x: bool,
+    pub invert_pan_y: bool,
     pub zoom_sensitivity: f32,
-    pub rotate_button: MouseButton,
-    pub pan_button: MouseButton,
+    /// Reverses zooming with the wheel. When unset, the wheel is reversed
+    /// while [`ScrollDirection`] reports natural scrolling, so the same
+    /// gesture zooms the same way whatever the system setting.
+    pub invert_zoom: Option<bool>,
+    /// Scale touch dragging, panning and pinching on top of the sensitivities
+    /// above. Twisting and pinching follow the fingers at the default of one.
+    pub touch_rotate_sensitivity: f32,
+    pub touch_pan_sensitivity: f32,
+    pub touch_zoom_sensitivity: f32,
+    /// Holding any of these keys scales wheel zooming by
+    /// `coarse_zoom_multiplier`.
+    pub coarse_zoom_modifiers: Vec<KeyCode>,
+    pub coarse_zoom_multiplier: f32,
+    pub gamepad: GamepadBindings,
+    pub control_scheme: ControlScheme,
+    pub rotate_binding: MouseBinding,
+    pub pan_binding: MouseBinding,
+    /// Dragging up and down with this zooms, if set.
+    pub zoom_binding: Option<MouseBinding>,
+    /// Where the built-in input comes from, for apps with several windows.
+    pub input_area: InputArea,
+    pub smoothing: Smoothing,
     pub enabled: bool,
 }
 
+/// Time constants, in seconds, for easing the camera towards its target.
+///
+/// After `tau` seconds the camera has covered about 63% of the remaining way.
+/// A value of zero disables smoothing for that channel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Smoothing {
+    pub rotate: f32,
+    pub pan: f32,
+    pub zoom: f32,
+}
+
+impl Smoothing {
+    pub const NONE: Smoothing = Smoothing {
+        rotate: 0.0,
+        pan: 0.0,
+        zoom: 0.0,
+    };
+
+    pub fn uniform(tau: f32) -> Self {
+        Smoothing {
+            rotate: tau,
+            pan: tau,
+            zoom: tau,
+        }
+    }
+
+    /// The same easing as a `dampingFactor` in three.js `OrbitControls`,
+    /// which covers that fraction of the remaining way every frame at 60 FPS.
+    /// Factors outside of zero to one disable smoothing.
+    pub fn from_damping_factor(factor: f32) -> Self {
+        if factor <= 0.0 || factor >= 1.0 {
+            return Smoothing::NONE;
+        }
+        Smoothing::uniform(-1.0 / (60.0 * (1.0 - factor).ln()))
+    }
+
+    /// The fraction of the remaining distance to cover over `dt` seconds.
+    pub fn factor(tau: f32, dt: f32) -> f32 {
+        if tau <= 0.0 {
+            1.0
+        } else {
+            1.0 - (-dt / tau).exp()
+        }
+    }
+}
+
+impl Default for Smoothing {
+    fn default() -> Self {
+        Smoothing::NONE
+    }
+}
+
+/// The pose currently applied to the camera, trailing [`OrbitCamera`] by the
+/// configured [`Smoothing`], and how it moved during the last frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitCameraState {
+    pub x: f32,
+    pub y: f32,
+    pub distance: f32,
+    pub center: Vec3,
+    pub up: Vec3,
+    pub fov: Option<f32>,
+    pub scale: Option<f32>,
+    /// Change in `x` and `y`, in radians.
+    pub last_rotation_delta: Vec2,
+    /// Change in `center`.
+    pub last_pan_delta: Vec3,
+    /// Ratio of the new `distance` to the old one.
+    pub last_zoom_factor: f32,
+}
+
+impl OrbitCameraState {
+    /// Whether the state has caught up with the camera's target.
+    pub fn reached(&self, camera: &OrbitCamera) -> bool {
+        self.x == camera.x
+            && self.y == camera.y
+            && self.distance == camera.distance
+            && self.center == camera.center
+            && self.up == camera.up
+            && self.fov == camera.fov
+            && self.scale == camera.scale
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.last_rotation_delta != Vec2::ZERO
+            || self.last_pan_delta != Vec3::ZERO
+            || self.last_zoom_factor != 1.0
+    }
+
+    pub fn transform(&self, mode: CameraMode) -> Transform {
+        let basis = up_rotation(self.up);
+        let up = basis * Vec3::Y;
+        let offset = basis * (orbit_rotation(self.x, self.y) * Vec3::Y);
+        match mode {
+            CameraMode::Orbit => {
+                let mut transform =
+                    Transform::from_translation(offset * self.distance + self.center);
+                transform.look_at(self.center, up);
+                transform
+            }
+            CameraMode::LookAround | CameraMode::Walk => {
+                let mut transform = Transform::from_translation(self.center);
+                transform.look_at(self.center - offset, up);
+                transform
+            }
+            CameraMode::Map => {
+                // Turned so that the map lines up with the orbit seen from above.
+                let rotation = basis
+                    * Quat::from_axis_angle(Vec3::Y, self.x + std::f32::consts::PI)
+                    * Quat::from_axis_angle(Vec3::X, -std::f32::consts::FRAC_PI_2);
+                Transform {
+                    translation: self.center + up * self.distance,
+                    rotation,
+                    ..Default::default()
+                }
+            }
+        }
+    }
+}
+
+impl From<&OrbitCamera> for OrbitCameraState {
+    fn from(camera: &OrbitCamera) -> Self {
+        OrbitCameraState {
+            x: camera.x,
+            y: camera.y,
+            distance: camera.distance,
+            center: camera.center,
+            up: camera.up,
+            fov: camera.fov,
+            scale: camera.scale,
+            last_rotation_delta: Vec2::ZERO,
+            last_pan_delta: Vec3::ZERO,
+            last_zoom_factor: 1.0,
+        }
+    }
+}
+
+/// The camera transform of the previous frame, for temporal effects such as
+/// motion blur and TAA. Inserted and updated by the plugin after it writes
+/// the transform, so it stays consistent even when the camera snaps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PreviousCameraTransform {
+    pub transform: Transform,
+    /// The inverse of `transform`, mapping world space to view space.
+    pub view_matrix: Mat4,
+    current: Transform,
+}
+
+impl PreviousCameraTransform {
+    fn new(transform: Transform) -> Self {
+        PreviousCameraTransform {
+            transform,
+            view_matrix: transform.compute_matrix().inverse(),
+            current: transform,
+        }
+    }
+
+    /// Moves the remembered positions along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        self.transform.translation += offset;
+        self.view_matrix = self.transform.compute_matrix().inverse();
+        self.current.translation += offset;
+    }
+}
+
+#[derive(SystemLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OrbitCameraSystem {
+    Input,
+    Update,
+    /// Keeps the targets set during `Update` within limits.
+    Limits,
+    Smoothing,
+    Transform,
+    Projection,
+}
+
 impl Default for OrbitCamera {
     fn default() -> Self {
         OrbitCamera {
+            mode: CameraMode::Orbit,
             x: 0.0,
             y: std::f32::consts::FRAC_PI_2,
             pitch_range: 0.01..=3.13,
+            yaw_range: f32::NEG_INFINITY..=f32::INFINITY,
             distance: 5.0,
+            distance_range: 0.01..=f32::INFINITY,
             center: Vec3::ZERO,
+            up: Vec3::Y,
+            fov: None,
+            fov_range: 0.1..=2.0,
+            scale: None,
+            scale_range: 0.01..=100.0,
+            rotation_locked: false,
+            rotation_pivot: RotationPivot::Center,
+            rotation_mode: RotationMode::Turntable,
+            drag_semantics: None,
+            resize_anchor: None,
             rotate_sensitivity: 1.0,
+            rotation_per_width: None,
+            adapt_rotation_to_fov: false,
             pan_sensitivity: 1.0,
+            distance_scaled_pan: false,
+            invert_pan_x: false,
+            invert_pan_y: false,
             zoom_sensitivity: 0.8,
-            rotate_button: MouseButton::Left,
-            pan_button: MouseButton::Right,
+            invert_zoom: None,
+            touch_rotate_sensitivity: 1.0,
+            touch_pan_sensitivity: 1.0,
+            touch_zoom_sensitivity: 1.0,
+            coarse_zoom_modifiers: vec![KeyCode::LShift, KeyCode::RShift],
+            coarse_zoom_multiplier: 10.0,
+            gamepad: GamepadBindings::default(),
+            control_scheme: ControlScheme::Standard,
+            rotate_binding: MouseButton::Left.into(),
+            pan_binding: MouseButton::Right.into(),
+            zoom_binding: None,
+            input_area: InputArea::default(),
+            smoothing: Smoothing::default(),
             enabled: true,
         }
     }
 }
 
 impl OrbitCamera {
+    /// The transform for the smoothed `state`, before any [`CameraOffset`].
+    pub fn controlled_transform(
+        &self,
+        state: &OrbitCameraState,
+        trackball: Option<&Trackball>,
+    ) -> Transform {
+        match trackball.filter(|_| self.mode == CameraMode::Orbit) {
+            Some(trackball) => trackball
+                .transform(state)
+                .unwrap_or_else(|| state.transform(self.mode)),
+            None => state.transform(self.mode),
+        }
+    }
+
+    /// Radians turned per pixel dragged in a window `window_width` wide.
+    pub fn rotation_per_pixel(&self, window_width: Option<f32>) -> f32 {
+        let ratio = match (self.rotation_per_width, window_width) {
+            (Some(rotation), Some(width)) if width > 0.0 => rotation / width,
+            _ => ROTATE_PIXEL_RATIO,
+        };
+        let zoom = match self.fov {
+            Some(fov) if self.adapt_rotation_to_fov => {
+                let reference = PerspectiveProjection::default().fov;
+                (fov / 2.0).tan() / (reference / 2.0).tan()
+            }
+            _ => 1.0,
+        };
+        self.rotate_sensitivity * ratio * zoom
+    }
+
+    /// World units panned per pixel dragged.
+    pub fn pan_per_pixel(&self) -> f32 {
+        let zoom = match (self.distance_scaled_pan, self.scale, self.mode) {
+            (false, _, _) => 1.0,
+            (true, Some(scale), _) => scale,
+            (true, None, CameraMode::Orbit) => self.distance / PAN_REFERENCE_DISTANCE,
+            (true, None, _) => 1.0,
+        };
+        self.pan_sensitivity * PAN_PIXEL_RATIO * zoom
+    }
+
+    /// Multiplies wheel zooming, reversing it if `invert_zoom` says so.
+    pub fn wheel_zoom_sign(&self, direction: &ScrollDirection) -> f32 {
+        match self.invert_zoom.or(direction.natural) {
+            Some(true) => -1.0,
+            _ => 1.0,
+        }
+    }
+
     pub fn new(dist: f32, center: Vec3) -> OrbitCamera {
         OrbitCamera {
             distance: dist,
@@ -84,69 +935,423 @@ impl OrbitCamera {
 
 pub struct OrbitCameraPlugin;
 impl OrbitCameraPlugin {
-    pub fn update_transform_system(
-        mut query: Query<(&OrbitCamera, &mut Transform), (Changed<OrbitCamera>, With<Camera>)>,
+    /// Derives the state of new cameras, and of cameras whose [`OrbitCamera`]
+    /// was replaced, such as when a scene is spawned over them, so they come
+    /// back exactly as saved instead of easing from where they were.
+    #[allow(clippy::type_complexity)]
+    pub fn init_state_system(
+        mut commands: Commands,
+        mut query: Query<
+            (
+                Entity,
+                &OrbitCamera,
+                &mut Transform,
+                Option<&mut OrbitCameraState>,
+            ),
+            Or<(Without<OrbitCameraState>, Added<OrbitCamera>)>,
+        >,
     ) {
-        for (camera, mut transform) in query.iter_mut() {
+        for (entity, camera, mut transform, existing) in query.iter_mut() {
+            let state = OrbitCameraState::from(camera);
             if camera.enabled {
-                let rot = Quat::from_axis_angle(Vec3::Y, camera.x)
-                    * Quat::from_axis_angle(-Vec3::X, camera.y);
-                transform.translation = (rot * Vec3::Y) * camera.distance + camera.center;
-                transform.look_at(camera.center, Vec3::Y);
+                *transform = state.transform(camera.mode);
+            }
+            match existing {
+                Some(mut existing) => *existing = state,
+                None => {
+                    commands.entity(entity).insert(state);
+                }
+            }
+            commands
+                .entity(entity)
+                .insert(PreviousCameraTransform::new(*transform));
+        }
+    }
+
+    /// Keeps `x` and `y` within `yaw_range` and `pitch_range` when they're set
+    /// directly.
+    pub fn angle_range_system(mut query: Query<(&mut OrbitCamera, Option<&Trackball>)>) {
+        for (mut camera, trackball) in query.iter_mut() {
+            // Tumbling freely goes over the poles.
+            if trackball.is_some() && camera.mode == CameraMode::Orbit {
+                continue;
+            }
+            let x = camera
+                .x
+                .max(*camera.yaw_range.start())
+                .min(*camera.yaw_range.end());
+            let y = camera
+                .y
+                .max(*camera.pitch_range.start())
+                .min(*camera.pitch_range.end());
+            if x != camera.x || y != camera.y {
+                camera.x = x;
+                camera.y = y;
+            }
+        }
+    }
+
+    pub fn distance_range_system(mut query: Query<&mut OrbitCamera>) {
+        for mut camera in query.iter_mut() {
+            let distance = camera
+                .distance
+                .max(*camera.distance_range.start())
+                .min(*camera.distance_range.end());
+            if distance != camera.distance {
+                camera.distance = distance;
+            }
+        }
+    }
+
+    pub fn smoothing_system(
+        time: Res<Time>,
+        mut query: Query<(&OrbitCamera, &mut OrbitCameraState), Without<CameraSuspended>>,
+    ) {
+        let dt = time.delta_seconds();
+        for (camera, mut state) in query.iter_mut() {
+            if !camera.enabled || state.reached(camera) {
+                if state.is_moving() {
+                    state.last_rotation_delta = Vec2::ZERO;
+                    state.last_pan_delta = Vec3::ZERO;
+                    state.last_zoom_factor = 1.0;
+                }
+                continue;
+            }
+            let rotate = Smoothing::factor(camera.smoothing.rotate, dt);
+            let pan = Smoothing::factor(camera.smoothing.pan, dt);
+            let zoom = Smoothing::factor(camera.smoothing.zoom, dt);
+
+            // Distance is approached geometrically, matching the multiplicative zoom.
+            let distance = if state.distance > 0.0 && camera.distance > 0.0 {
+                state.distance * (camera.distance / state.distance).powf(zoom)
+            } else {
+                camera.distance
+            };
+            let mut next = OrbitCameraState {
+                x: state.x + (camera.x - state.x) * rotate,
+                y: state.y + (camera.y - state.y) * rotate,
+                distance,
+                center: state.center.lerp(camera.center, pan),
+                up: camera.up,
+                fov: ease_geometric(state.fov, camera.fov, zoom),
+                scale: ease_geometric(state.scale, camera.scale, zoom),
+                ..*state
+            };
+            if (camera.x - next.x).abs() < SNAP_EPSILON && (camera.y - next.y).abs() < SNAP_EPSILON
+            {
+                next.x = camera.x;
+                next.y = camera.y;
+            }
+            if (camera.distance - next.distance).abs() < SNAP_EPSILON * camera.distance.max(1.0) {
+                next.distance = camera.distance;
+            }
+            if camera.center.distance(next.center) < SNAP_EPSILON * camera.distance.max(1.0) {
+                next.center = camera.center;
+            }
+            if let (Some(fov), Some(target)) = (next.fov, camera.fov) {
+                if (fov - target).abs() < SNAP_EPSILON {
+                    next.fov = camera.fov;
+                }
+            }
+            if let (Some(scale), Some(target)) = (next.scale, camera.scale) {
+                if (scale - target).abs() < SNAP_EPSILON * target {
+                    next.scale = camera.scale;
+                }
+            }
+            next.last_rotation_delta = Vec2::new(next.x - state.x, next.y - state.y);
+            next.last_pan_delta = next.center - state.center;
+            next.last_zoom_factor = if state.distance > 0.0 {
+                next.distance / state.distance
+            } else {
+                1.0
+            };
+            *state = next;
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn update_transform_system(
+        mut query: Query<
+            (
+                &OrbitCamera,
+                &OrbitCameraState,
+                &mut Transform,
+                Option<&Trackball>,
+                Option<&CameraOffset>,
+                Option<&PitchDistance>,
+                Option<&CameraBlendStack>,
+                Option<&CameraCollision>,
+            ),
+            (
+                Or<(
+                    Changed<OrbitCameraState>,
+                    Changed<Trackball>,
+                    Changed<CameraOffset>,
+                    Changed<CameraBlendStack>,
+                    Changed<CameraCollision>,
+                )>,
+                With<Camera>,
+            ),
+        >,
+    ) {
+        for (camera, state, mut transform, trackball, offset, crane, stack, collision) in
+            query.iter_mut()
+        {
+            if !camera.enabled {
+                continue;
+            }
+            let state = shown_state(state, crane, stack, collision);
+            let controlled = camera.controlled_transform(&state, trackball);
+            *transform = match offset {
+                Some(offset) => offset.apply(&controlled),
+                None => controlled,
+            };
+        }
+    }
+
+    pub fn previous_transform_system(
+        mut query: Query<(&Transform, &mut PreviousCameraTransform), With<OrbitCamera>>,
+    ) {
+        for (transform, mut previous) in query.iter_mut() {
+            previous.transform = previous.current;
+            previous.view_matrix = previous.transform.compute_matrix().inverse();
+            previous.current = *transform;
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn update_projection_system(
+        mut query: Query<
+            (
+                &OrbitCamera,
+                &OrbitCameraState,
+                &mut Camera,
+                Option<&mut PerspectiveProjection>,
+                Option<&mut OrthographicProjection>,
+            ),
+            Changed<OrbitCameraState>,
+        >,
+    ) {
+        for (camera, state, mut render_camera, perspective, orthographic) in query.iter_mut() {
+            if !camera.enabled {
+                continue;
+            }
+            if let (Some(fov), Some(mut projection)) = (state.fov, perspective) {
+                if projection.fov != fov {
+                    projection.fov = fov;
+                    render_camera.projection_matrix = projection.get_projection_matrix();
+                }
+            }
+            if let (Some(scale), Some(mut projection)) = (state.scale, orthographic) {
+                if projection.scale != scale {
+                    projection.scale = scale;
+                    render_camera.projection_matrix = projection.get_projection_matrix();
+                }
             }
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn emit_motion_events(
         mut events: EventWriter<CameraEvents>,
+        mut routed: EventWriter<RoutedCameraEvents>,
         mut mouse_motion_events: EventReader<MouseMotion>,
+        mut outside: Local<HashSet<Entity>>,
         mouse_button_input: Res<Input<MouseButton>>,
-        mut query: Query<&OrbitCamera>,
+        keyboard_input: Res<Input<KeyCode>>,
+        recorder: Res<InputRecorder>,
+        capture: Res<InputCapture>,
+        ui: Res<UiPointerCapture>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        windows: Res<Windows>,
+        mut query: Query<(
+            Entity,
+            &OrbitCamera,
+            Option<&Camera>,
+            Option<&InputExclusions>,
+        )>,
     ) {
         let mut delta = Vec2::ZERO;
         for event in mouse_motion_events.iter() {
             delta += event.delta;
         }
-        for camera in query.iter_mut() {
+        if recorder.is_replaying() || capture.pointer_taken(&ui) {
+            return;
+        }
+        let mut input = CameraInput::default();
+        for (entity, camera, render_camera, exclusions) in query.iter_mut() {
+            let sent = input.camera(entity, camera.input_area, active.is_some());
+            if exclusions.map_or(false, |exclusions| exclusions.is_drag_blocked())
+                || !receives_input(active.as_deref(), entity)
+            {
+                continue;
+            }
+            // Drags belong to the area they start in, wherever they go.
+            let area = camera.input_area;
+            if area.is_scoped() {
+                let started = camera
+                    .drag_buttons()
+                    .iter()
+                    .any(|button| mouse_button_input.just_pressed(*button));
+                if started && area.has_cursor(&windows, render_camera) {
+                    outside.remove(&entity);
+                } else if started {
+                    outside.insert(entity);
+                }
+                if outside.contains(&entity) {
+                    continue;
+                }
+            }
             if camera.enabled {
-                if mouse_button_input.pressed(camera.rotate_button) {
-                    events.send(CameraEvents::Orbit(delta))
+                let drags = camera.drags(&mouse_button_input, &keyboard_input);
+                if drags.rotate {
+                    sent.push(match camera.control_scheme {
+                        ControlScheme::Standard => CameraEvents::Orbit(delta),
+                        ControlScheme::ScrollToRotate => {
+                            CameraEvents::Zoom(-delta.y * DRAG_ZOOM_RATIO)
+                        }
+                    })
                 }
 
-                if mouse_button_input.pressed(camera.pan_button) {
-                    events.send(CameraEvents::Pan(delta))
+                if drags.pan {
+                    sent.push(CameraEvents::Pan(delta))
+                }
+
+                if drags.zoom {
+                    sent.push(CameraEvents::Zoom(-delta.y * DRAG_ZOOM_RATIO))
                 }
             }
         }
+        input.send(&mut events, &mut routed);
     }
 
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
     pub fn mouse_motion_system(
-        time: Res<Time>,
         mut events: EventReader<CameraEvents>,
-        mut query: Query<(&mut OrbitCamera, &mut Transform, &mut Camera)>,
+        mut routed: EventReader<RoutedCameraEvents>,
+        mut feedback: EventWriter<CameraFeedback>,
+        mut query: Query<(
+            Entity,
+            &mut OrbitCamera,
+            &mut Transform,
+            &mut Camera,
+            Option<&InputFilter>,
+            Option<&DragPivot>,
+            Option<&mut Trackball>,
+            Option<&CameraCollision>,
+            Option<&OrbitTransition>,
+        )>,
+        windows: Res<Windows>,
+        raycaster: Res<Raycaster>,
+        drag_semantics: Res<DragSemantics>,
+        budget: Res<FrameBudget>,
+        order: Res<ApplicationOrder>,
     ) {
-        for (mut camera, transform, _) in query.iter_mut() {
-            if !camera.enabled {
+        let clamp = order.limits == LimitOrder::BeforeSmoothing;
+        let events: Vec<CameraEvents> = events.iter().copied().collect();
+        let routed: Vec<RoutedCameraEvents> = routed.iter().copied().collect();
+        for (
+            entity,
+            mut camera,
+            transform,
+            render_camera,
+            filter,
+            pivot,
+            mut trackball,
+            collision,
+            transition,
+        ) in query.iter_mut()
+        {
+            if !camera.enabled || transition.map_or(false, OrbitTransition::locks_input) {
                 continue;
             }
+            let rotation_per_pixel =
+                camera.rotation_per_pixel(window_width(&windows, &render_camera));
 
-            for event in events.iter() {
-                match event {
-                    CameraEvents::Orbit(delta) => {
-                        camera.x -= delta.x * camera.rotate_sensitivity * time.delta_seconds();
-                        camera.y -= delta.y * camera.rotate_sensitivity * time.delta_seconds();
-                        camera.y = camera
+            // Looking around drags the view itself, so the scene follows the
+            // cursor the way it does when orbiting.
+            let sign = match camera.mode {
+                CameraMode::Orbit | CameraMode::Map => 1.0,
+                CameraMode::LookAround | CameraMode::Walk => -1.0,
+            };
+            let drag = match camera.drag_semantics.unwrap_or(*drag_semantics) {
+                DragSemantics::GrabWorld => 1.0,
+                DragSemantics::MoveCamera => -1.0,
+            };
+            let own = routed
+                .iter()
+                .filter(|routed| routed.camera == entity)
+                .map(|routed| &routed.event);
+            // Scoped cameras leave the events sent to every camera alone.
+            let broadcast = (!camera.input_area.is_scoped()).then(|| events.iter());
+            let mut own: Vec<&CameraEvents> = broadcast.into_iter().flatten().chain(own).collect();
+            order.motion.sort(&mut own);
+            for event in own {
+                match filtered(filter, event) {
+                    Some(CameraEvents::Orbit(_)) if camera.rotation_locked => {}
+                    Some(CameraEvents::Orbit(delta)) => {
+                        let delta = delta * drag;
+                        if let (CameraMode::Orbit, Some(trackball)) = (camera.mode, &mut trackball)
+                        {
+                            trackball.rotate(&mut camera, delta * rotation_per_pixel);
+                            continue;
+                        }
+                        let from = (camera.x, camera.y);
+                        camera.x -= sign * delta.x * rotation_per_pixel;
+                        camera.y -= sign * delta.y * rotation_per_pixel;
+                        let pitch = camera
                             .y
                             .max(*camera.pitch_range.start())
                             .min(*camera.pitch_range.end());
+                        if clamp && pitch != camera.y {
+                            camera.y = pitch;
+                            feedback.send(CameraFeedback::limit(entity, CameraLimit::Pitch));
+                        }
+                        let yaw = camera
+                            .x
+                            .max(*camera.yaw_range.start())
+                            .min(*camera.yaw_range.end());
+                        if clamp && yaw != camera.x {
+                            camera.x = yaw;
+                            feedback.send(CameraFeedback::limit(entity, CameraLimit::Yaw));
+                        }
+                        if let (CameraMode::Orbit, Some(pivot)) = (camera.mode, pivot) {
+                            let to = (camera.x, camera.y);
+                            camera.center =
+                                rotate_about(camera.center, pivot.0, camera.up, from, to);
+                        }
                     }
-                    CameraEvents::Pan(delta) => {
+                    Some(CameraEvents::Pan(delta)) => {
+                        let invert = |inverted| if inverted { -1.0 } else { 1.0 };
+                        let delta = delta
+                            * drag
+                            * Vec2::new(invert(camera.invert_pan_x), invert(camera.invert_pan_y));
                         let right_dir = transform.rotation * -Vec3::X;
-                        let up_dir = transform.rotation * Vec3::Y;
-                        let pan_vector = (delta.x * right_dir + delta.y * up_dir)
-                            * camera.pan_sensitivity
-                            * time.delta_seconds();
-                        camera.center += pan_vector;
+                        // Walking slides along the ground instead of rising.
+                        let up_dir = match camera.mode {
+                            CameraMode::Walk => walk_forward(&camera),
+                            _ => transform.rotation * Vec3::Y,
+                        };
+                        let pan_vector =
+                            (delta.x * right_dir + delta.y * up_dir) * camera.pan_per_pixel();
+                        match collision.filter(|collision| collision.pan) {
+                            Some(collision) => {
+                                let (center, hit) = collision.slide(
+                                    &raycaster,
+                                    camera.center,
+                                    pan_vector,
+                                    budget.collision_casts,
+                                );
+                                camera.center = center;
+                                if hit {
+                                    feedback.send(CameraFeedback {
+                                        camera: entity,
+                                        kind: FeedbackKind::CollisionCorrected,
+                                    });
+                                }
+                            }
+                            None => camera.center += pan_vector,
+                        }
                     }
                     _ => {}
                 }
@@ -154,10 +1359,24 @@ impl OrbitCameraPlugin {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn emit_zoom_events(
         mut events: EventWriter<CameraEvents>,
+        mut routed: EventWriter<RoutedCameraEvents>,
         mut mouse_wheel_events: EventReader<MouseWheel>,
-        mut query: Query<&OrbitCamera>,
+        keyboard_input: Res<Input<KeyCode>>,
+        recorder: Res<InputRecorder>,
+        capture: Res<InputCapture>,
+        ui: Res<UiPointerCapture>,
+        windows: Res<Windows>,
+        scroll_direction: Res<ScrollDirection>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        mut query: Query<(
+            Entity,
+            &OrbitCamera,
+            Option<&Camera>,
+            Option<&InputExclusions>,
+        )>,
     ) {
         let mut total = 0.0;
         for event in mouse_wheel_events.iter() {
@@ -168,37 +1387,743 @@ impl OrbitCameraPlugin {
                 };
         }
 
-        if total != 0.0 {
-            for camera in query.iter_mut() {
-                if camera.enabled {
-                    events.send(CameraEvents::Zoom(total));
+        if recorder.is_replaying() {
+            return;
+        }
+        let shift =
+            keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift);
+        let mut keys = 0.0;
+        if keyboard_input.just_pressed(KeyCode::Equals)
+            || keyboard_input.just_pressed(KeyCode::NumpadAdd)
+        {
+            keys += 1.0;
+        }
+        if keyboard_input.just_pressed(KeyCode::Minus)
+            || keyboard_input.just_pressed(KeyCode::NumpadSubtract)
+        {
+            keys -= 1.0;
+        }
+        if capture.keyboard {
+            keys = 0.0;
+        }
+        let mut input = CameraInput::default();
+        for (entity, camera, render_camera, exclusions) in query.iter_mut() {
+            let sent = input.camera(entity, camera.input_area, active.is_some());
+            if !camera.enabled || !receives_input(active.as_deref(), entity) {
+                continue;
+            }
+            let area = camera.input_area;
+            let excluded = exclusions.map_or(false, |exclusions| {
+                cursor_position(&windows, render_camera)
+                    .map_or(false, |position| exclusions.contains(position))
+            });
+            let total = if excluded
+                || capture.pointer_taken(&ui)
+                || !area.has_cursor(&windows, render_camera)
+            {
+                0.0
+            } else {
+                total
+            };
+            let keys = if area.has_keyboard(&windows, render_camera) {
+                keys
+            } else {
+                0.0
+            };
+            if camera.control_scheme == ControlScheme::ScrollToRotate {
+                if total != 0.0 {
+                    let turn = total * WHEEL_ROTATE_PIXELS;
+                    sent.push(CameraEvents::Orbit(if shift {
+                        Vec2::new(0.0, turn)
+                    } else {
+                        Vec2::new(turn, 0.0)
+                    }));
+                }
+                if keys != 0.0 {
+                    sent.push(CameraEvents::Zoom(keys));
                 }
+                continue;
+            }
+            if total != 0.0 {
+                let coarse = camera
+                    .coarse_zoom_modifiers
+                    .iter()
+                    .any(|key| keyboard_input.pressed(*key));
+                let multiplier = if coarse {
+                    camera.coarse_zoom_multiplier
+                } else {
+                    1.0
+                };
+                let sign = camera.wheel_zoom_sign(&scroll_direction);
+                sent.push(CameraEvents::Zoom(total * multiplier * sign));
             }
         }
+        input.send(&mut events, &mut routed);
     }
 
+    #[allow(clippy::type_complexity)]
     pub fn zoom_system(
-        mut query: Query<&mut OrbitCamera, With<Camera>>,
+        mut feedback: EventWriter<CameraFeedback>,
+        mut query: Query<(
+            Entity,
+            &mut OrbitCamera,
+            &Camera,
+            &GlobalTransform,
+            Option<&PerspectiveProjection>,
+            Option<&OrthographicProjection>,
+            Option<&InputFilter>,
+            Option<&WalkConstraint>,
+            Option<&OrbitTransition>,
+        )>,
         mut events: EventReader<CameraEvents>,
+        mut routed: EventReader<RoutedCameraEvents>,
+        windows: Res<Windows>,
+        raycaster: Res<Raycaster>,
+        order: Res<ApplicationOrder>,
     ) {
-        for mut camera in query.iter_mut() {
-            for event in events.iter() {
-                if camera.enabled {
-                    if let CameraEvents::Zoom(distance) = event {
-                        camera.distance *= camera.zoom_sensitivity.powf(*distance);
+        let events: Vec<CameraEvents> = events.iter().copied().collect();
+        let routed: Vec<RoutedCameraEvents> = routed.iter().copied().collect();
+        for (
+            entity,
+            mut camera,
+            render_camera,
+            transform,
+            perspective,
+            orthographic,
+            filter,
+            walk,
+            transition,
+        ) in query.iter_mut()
+        {
+            let locked = transition.map_or(false, OrbitTransition::locks_input);
+            let own = routed
+                .iter()
+                .filter(|routed| routed.camera == entity)
+                .map(|routed| &routed.event);
+            let broadcast = (!camera.input_area.is_scoped()).then(|| events.iter());
+            for event in broadcast.into_iter().flatten().chain(own) {
+                if camera.enabled && !locked {
+                    if let Some(CameraEvents::Zoom(distance)) = filtered(filter, event) {
+                        let factor = camera.zoom_sensitivity.powf(distance);
+                        match camera.mode {
+                            // Moving an orthographic camera doesn't change
+                            // what it shows, so zoom the projection instead.
+                            CameraMode::Orbit if orthographic.is_some() => {
+                                if let Some((scale, new_scale)) =
+                                    zoom_scale(&camera, orthographic, factor)
+                                {
+                                    if new_scale != scale * factor {
+                                        feedback
+                                            .send(CameraFeedback::limit(entity, CameraLimit::Zoom));
+                                    }
+                                    camera.scale = Some(new_scale);
+                                }
+                            }
+                            CameraMode::Orbit => {
+                                let distance = camera.distance * factor;
+                                camera.distance = match order.limits {
+                                    LimitOrder::BeforeSmoothing => distance
+                                        .max(*camera.distance_range.start())
+                                        .min(*camera.distance_range.end()),
+                                    LimitOrder::AfterSmoothing => distance,
+                                };
+                                if camera.distance != distance {
+                                    feedback
+                                        .send(CameraFeedback::limit(entity, CameraLimit::Distance));
+                                }
+                            }
+                            CameraMode::Walk => {
+                                let step = walk.map_or(1.0, |walk| walk.step);
+                                let forward = walk_forward(&camera);
+                                camera.center += forward * distance * step;
+                            }
+                            CameraMode::LookAround => {
+                                let fov = camera.fov.or_else(|| perspective.map(|p| p.fov));
+                                if let Some(fov) = fov {
+                                    let new_fov = (fov * factor)
+                                        .max(*camera.fov_range.start())
+                                        .min(*camera.fov_range.end());
+                                    if new_fov != fov * factor {
+                                        feedback
+                                            .send(CameraFeedback::limit(entity, CameraLimit::Zoom));
+                                    }
+                                    camera.fov = Some(new_fov);
+                                }
+                            }
+                            CameraMode::Map => {
+                                let (scale, new_scale) =
+                                    match zoom_scale(&camera, orthographic, factor) {
+                                        Some(scales) => scales,
+                                        None => continue,
+                                    };
+                                if new_scale != scale * factor {
+                                    feedback.send(CameraFeedback::limit(entity, CameraLimit::Zoom));
+                                }
+                                // Keep the point under the cursor where it is.
+                                let anchor = windows
+                                    .get(render_camera.window)
+                                    .and_then(|window| window.cursor_position())
+                                    .and_then(|cursor| {
+                                        pick(
+                                            &raycaster,
+                                            entity,
+                                            &camera,
+                                            render_camera,
+                                            transform,
+                                            &windows,
+                                            cursor,
+                                        )
+                                    });
+                                if let Some(anchor) = anchor {
+                                    // Along the map, whatever the height of the hit.
+                                    let up = up_rotation(camera.up) * Vec3::Y;
+                                    let offset = anchor - camera.center;
+                                    let offset = offset - up * offset.dot(up);
+                                    camera.center += offset * (1.0 - new_scale / scale);
+                                }
+                                camera.scale = Some(new_scale);
+                            }
+                        }
                     }
                 }
             }
         }
     }
 }
+
+fn window_width(windows: &Windows, camera: &Camera) -> Option<f32> {
+    windows.get(camera.window).map(|window| window.width())
+}
+
+/// The orthographic scale before and after zooming by `factor`, within
+/// `scale_range`.
+fn zoom_scale(
+    camera: &OrbitCamera,
+    orthographic: Option<&OrthographicProjection>,
+    factor: f32,
+) -> Option<(f32, f32)> {
+    let scale = camera.scale.or_else(|| orthographic.map(|o| o.scale))?;
+    let new_scale = (scale * factor)
+        .max(*camera.scale_range.start())
+        .min(*camera.scale_range.end());
+    Some((scale, new_scale))
+}
+
+/// `state` with any [`CameraBlendStack`] mixed in, pulled in by the spring arm
+/// of any [`CameraCollision`].
+pub(crate) fn shown_state(
+    state: &OrbitCameraState,
+    crane: Option<&PitchDistance>,
+    stack: Option<&CameraBlendStack>,
+    collision: Option<&CameraCollision>,
+) -> OrbitCameraState {
+    let mut state = *state;
+    if let Some(crane) = crane {
+        state.distance *= crane.scale(state.y);
+    }
+    let mut state = stack.map_or(state, |stack| stack.blend(&state));
+    if let Some(arm) = collision.and_then(|collision| collision.arm()) {
+        state.distance = state.distance.min(arm);
+    }
+    state
+}
+
+/// Eases `from` towards `to` by `factor`, geometrically for values like the
+/// field of view and scale that are zoomed multiplicatively.
+fn ease_geometric(from: Option<f32>, to: Option<f32>, factor: f32) -> Option<f32> {
+    match (from, to) {
+        (Some(from), Some(to)) if from > 0.0 && to > 0.0 => Some(from * (to / from).powf(factor)),
+        (_, to) => to,
+    }
+}
+
 impl Plugin for OrbitCameraPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.add_system(Self::emit_motion_events.system())
-            .add_system(Self::mouse_motion_system.system())
-            .add_system(Self::emit_zoom_events.system())
-            .add_system(Self::zoom_system.system())
-            .add_system(Self::update_transform_system.system())
-            .add_event::<CameraEvents>();
+        let order = app
+            .world()
+            .get_resource::<ApplicationOrder>()
+            .copied()
+            .unwrap_or_default();
+        let zoom = Self::zoom_system
+            .system()
+            .label(OrbitCameraSystem::Update)
+            .after(OrbitCameraSystem::Input);
+        app.insert_resource(order)
+            .add_system(
+                Self::mouse_motion_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .label(DeltaSystem::Motion)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(match order.zoom {
+                ZoomOrder::BeforeMotion => zoom.before(DeltaSystem::Motion),
+                ZoomOrder::AfterMotion => zoom.after(DeltaSystem::Motion),
+            });
+        match order.limits {
+            LimitOrder::BeforeSmoothing => app
+                .add_system(
+                    Self::distance_range_system
+                        .system()
+                        .label(OrbitCameraSystem::Limits)
+                        .label(LimitSystem::Distance)
+                        .after(OrbitCameraSystem::Update)
+                        .after(LimitSystem::Frustum),
+                )
+                .add_system(
+                    Self::angle_range_system
+                        .system()
+                        .label(OrbitCameraSystem::Limits)
+                        .label(LimitSystem::Angles)
+                        .after(OrbitCameraSystem::Update)
+                        .after(LimitSystem::Distance)
+                        .before(LimitSystem::DistancePitch),
+                ),
+            LimitOrder::AfterSmoothing => app
+                .add_system(
+                    Self::distance_range_system
+                        .system()
+                        .label(LimitSystem::Distance)
+                        .after(OrbitCameraSystem::Smoothing),
+                )
+                .add_system(
+                    Self::angle_range_system
+                        .system()
+                        .label(LimitSystem::Angles)
+                        .after(OrbitCameraSystem::Smoothing)
+                        .after(LimitSystem::Distance),
+                ),
+        };
+
+        let input = app
+            .world()
+            .get_resource::<BuiltInInput>()
+            .copied()
+            .unwrap_or_default();
+        app.insert_resource(input);
+        if input.mouse {
+            app.add_system(
+                Self::emit_motion_events
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::emit_zoom_events
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::emit_focus_events
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            );
+        }
+
+        app.register_type::<OrbitCamera>()
+            .add_system(
+                Self::frame_budget_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::world_origin_shift_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::ui_capture_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::active_camera_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::emit_keyboard_events
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::emit_continuous_zoom_events
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::last_input_device_system
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::replay_input_system
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::record_input_system
+                    .system()
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::drag_pivot_system
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::focus_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::focus_cycle_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::keyboard_step_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::primary_camera_apply_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::tilt_parallax_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::camera_path_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::dolly_zoom_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::flick_turn_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::transition_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::follow_target_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::resize_anchor_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::camera_command_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::frame_command_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::camera_zone_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::override_stack_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::view_preset_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::history_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(Self::init_state_system.system())
+            .add_system(
+                Self::suspend_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::rotation_mode_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::input_exclusions_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::validate_system
+                    .system()
+                    .after(OrbitCameraSystem::Limits),
+            )
+            .add_system(
+                Self::command_log_apply_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::command_log_capture_system
+                    .system()
+                    .after(OrbitCameraSystem::Limits)
+                    .before(OrbitCameraSystem::Smoothing),
+            )
+            .add_system(
+                Self::scene_bounds_system
+                    .system()
+                    .before(OrbitCameraSystem::Limits),
+            )
+            .add_system(
+                Self::scene_boundary_system
+                    .system()
+                    .label(OrbitCameraSystem::Limits)
+                    .label(LimitSystem::SceneBoundary)
+                    .after(OrbitCameraSystem::Update)
+                    .after(LimitSystem::Walk),
+            )
+            .add_system(
+                Self::frustum_distance_limit_system
+                    .system()
+                    .label(OrbitCameraSystem::Limits)
+                    .label(LimitSystem::Frustum)
+                    .after(OrbitCameraSystem::Update)
+                    .after(LimitSystem::Whiskers),
+            )
+            .add_system(
+                Self::distance_pitch_limit_system
+                    .system()
+                    .label(OrbitCameraSystem::Limits)
+                    .label(LimitSystem::DistancePitch)
+                    .after(OrbitCameraSystem::Update)
+                    .after(LimitSystem::Frustum),
+            )
+            .add_system(
+                Self::whiskers_system
+                    .system()
+                    .label(OrbitCameraSystem::Limits)
+                    .label(LimitSystem::Whiskers)
+                    .after(OrbitCameraSystem::Update)
+                    .after(LimitSystem::SceneBoundary),
+            )
+            .add_system(
+                Self::walk_constraint_system
+                    .system()
+                    .label(OrbitCameraSystem::Limits)
+                    .label(LimitSystem::Walk)
+                    .after(OrbitCameraSystem::Update)
+                    .after(LimitSystem::Rail),
+            )
+            .add_system(
+                Self::camera_rail_system
+                    .system()
+                    .label(OrbitCameraSystem::Limits)
+                    .label(LimitSystem::Rail)
+                    .after(OrbitCameraSystem::Update),
+            )
+            .add_system(
+                Self::trackball_system
+                    .system()
+                    .label(OrbitCameraSystem::Smoothing)
+                    .after(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Limits),
+            )
+            .add_system(
+                Self::smoothing_system
+                    .system()
+                    .label(OrbitCameraSystem::Smoothing)
+                    .after(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Limits),
+            )
+            .add_system(
+                Self::spring_arm_system
+                    .system()
+                    .after(OrbitCameraSystem::Smoothing)
+                    .before(OrbitCameraSystem::Transform),
+            )
+            .add_system(
+                Self::camera_blend_stack_system
+                    .system()
+                    .label(OrbitCameraSystem::Smoothing)
+                    .after(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Limits),
+            )
+            .add_system(
+                Self::update_transform_system
+                    .system()
+                    .label(OrbitCameraSystem::Transform)
+                    .after(OrbitCameraSystem::Smoothing),
+            )
+            .add_system(
+                Self::camera_activity_system
+                    .system()
+                    .after(OrbitCameraSystem::Smoothing),
+            )
+            .add_system(
+                Self::interaction_summary_system
+                    .system()
+                    .after(OrbitCameraSystem::Smoothing),
+            )
+            .add_system(
+                Self::camera_offset_removed_system
+                    .system()
+                    .label(OrbitCameraSystem::Transform)
+                    .after(OrbitCameraSystem::Smoothing),
+            )
+            .add_system(
+                Self::primary_camera_mirror_system
+                    .system()
+                    .after(OrbitCameraSystem::Transform),
+            )
+            .add_system(
+                Self::previous_transform_system
+                    .system()
+                    .after(OrbitCameraSystem::Transform),
+            )
+            .add_system(
+                Self::update_projection_system
+                    .system()
+                    .label(OrbitCameraSystem::Transform)
+                    .after(OrbitCameraSystem::Smoothing),
+            )
+            .add_system(
+                Self::projection_switch_system
+                    .system()
+                    .label(OrbitCameraSystem::Projection)
+                    .after(OrbitCameraSystem::Transform),
+            )
+            .add_system(
+                Self::viewport_margins_system
+                    .system()
+                    .after(OrbitCameraSystem::Projection),
+            )
+            .init_resource::<TouchSettings>()
+            .init_resource::<DoubleClickSettings>()
+            .init_resource::<DragSemantics>()
+            .init_resource::<OrbitGamepadSettings>()
+            .init_resource::<LastInputDevice>()
+            .init_resource::<InputRecorder>()
+            .init_resource::<InputCapture>()
+            .init_resource::<CameraOverrideStacks>()
+            .init_resource::<UiPointerCapture>()
+            .init_resource::<Raycaster>()
+            .init_resource::<DeviceOrientation>()
+            .init_resource::<SceneBounds>()
+            .init_resource::<ScrollDirection>()
+            .init_resource::<FrameBudget>()
+            .init_resource::<InteractionSummarySettings>()
+            .init_resource::<SuspendSettings>()
+            .add_event::<CameraEvents>()
+            .add_event::<RoutedCameraEvents>()
+            .add_event::<TouchGestureEvent>()
+            .add_event::<FocusRipple>()
+            .add_event::<FocusRequested>()
+            .add_event::<CameraHistoryCommand>()
+            .add_event::<KeyframeCrossed>()
+            .add_event::<CameraSuspendEvent>()
+            .add_event::<CameraFeedback>()
+            .add_event::<TargetTeleported>()
+            .add_event::<TargetLost>()
+            .add_event::<CameraActivity>()
+            .add_event::<InteractionSummary>()
+            .add_event::<OrbitCameraCommand>()
+            .add_event::<FrameCommand>()
+            .add_event::<WorldOriginShifted>()
+            .add_asset::<CameraPath>();
+
+        #[cfg(feature = "touch")]
+        if input.touch {
+            app.add_system(
+                Self::emit_touch_events
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            )
+            .add_system(
+                Self::long_press_focus_system
+                    .system()
+                    .label(OrbitCameraSystem::Update)
+                    .after(OrbitCameraSystem::Input),
+            );
+        }
+
+        #[cfg(feature = "gamepad")]
+        if input.gamepad {
+            app.add_system(
+                Self::emit_gamepad_events
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            );
+        }
+
+        #[cfg(all(feature = "device_orientation", target_arch = "wasm32"))]
+        app.init_resource::<BrowserOrientation>()
+            .add_startup_system(Self::listen_device_orientation_system.system())
+            .add_system(
+                Self::browser_orientation_system
+                    .system()
+                    .label(OrbitCameraSystem::Input),
+            );
+
+        #[cfg(all(feature = "natural_scrolling", target_arch = "wasm32"))]
+        app.init_resource::<BrowserScrollDirection>()
+            .add_startup_system(Self::listen_scroll_direction_system.system())
+            .add_system(
+                Self::browser_scroll_direction_system
+                    .system()
+                    .before(OrbitCameraSystem::Input),
+            );
+
+        #[cfg(all(feature = "natural_scrolling", target_os = "macos"))]
+        app.add_startup_system(Self::macos_scroll_direction_system.system());
+
+        #[cfg(feature = "touch_emulation")]
+        app.init_resource::<TouchEmulation>().add_system(
+            Self::touch_emulation_system
+                .system()
+                .before(OrbitCameraSystem::Input),
+        );
+
+        #[cfg(all(feature = "url_hash", target_arch = "wasm32"))]
+        app.add_system(
+            Self::url_hash_system
+                .system()
+                .label(OrbitCameraSystem::Update)
+                .after(OrbitCameraSystem::Input),
+        );
     }
 }
@@ -0,0 +1,72 @@
+//! Loading data saved by older versions of a format.
+
+use bevy::utils::HashMap;
+use std::fmt;
+
+type Step<T> = Box<dyn Fn(T) -> T + Send + Sync>;
+
+/// Why saved data couldn't be brought up to date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MigrationError {
+    /// The data was written by a newer version than this build knows.
+    TooNew(u32),
+    /// No migration is registered from this version.
+    MissingStep(u32),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::TooNew(version) => write!(f, "version {} is too new", version),
+            MigrationError::MissingStep(version) => {
+                write!(f, "no migration from version {}", version)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// Upgrades saved data one version at a time until it reaches `current`, so
+/// settings persisted by an older release of an app keep loading. Each step
+/// takes the data as written by one version and returns it as the next
+/// version would have written it.
+pub struct Migrations<T> {
+    current: u32,
+    steps: HashMap<u32, Step<T>>,
+}
+
+impl<T> Migrations<T> {
+    pub fn new(current: u32) -> Self {
+        Migrations {
+            current,
+            steps: HashMap::default(),
+        }
+    }
+
+    /// The version data is migrated to.
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Adds the step upgrading data from version `from` to `from + 1`.
+    pub fn step(mut self, from: u32, step: impl Fn(T) -> T + Send + Sync + 'static) -> Self {
+        self.steps.insert(from, Box::new(step));
+        self
+    }
+
+    /// Brings `data`, written by `version`, up to the current version.
+    pub fn migrate(&self, version: u32, mut data: T) -> Result<T, MigrationError> {
+        if version > self.current {
+            return Err(MigrationError::TooNew(version));
+        }
+        for from in version..self.current {
+            let step = self
+                .steps
+                .get(&from)
+                .ok_or(MigrationError::MissingStep(from))?;
+            data = step(data);
+        }
+        Ok(data)
+    }
+}
@@ -0,0 +1,105 @@
+//! A [`RaycastProvider`] backed by `bevy_mod_raycast`.
+
+use crate::{OrbitCamera, RaycastProvider, RaycastQuery, Raycaster};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+use bevy::utils::HashMap;
+use bevy_mod_raycast::{DefaultRaycastingPlugin, RayCastMethod, RayCastSource, RaycastSystem};
+use std::sync::{Arc, RwLock};
+
+/// How far, in pixels, a query may be from the last cast and still use it.
+const PICK_TOLERANCE: f32 = 1.0;
+
+/// The `bevy_mod_raycast` set picked by the orbit camera. Mark meshes with
+/// `RayCastMesh<OrbitRaycastSet>` to make them focusable.
+pub struct OrbitRaycastSet;
+
+/// Answers queries from the most recent cast under the pointer of each
+/// camera, which `bevy_mod_raycast` updates once a frame.
+#[derive(Clone, Default)]
+pub struct ModRaycast {
+    hits: Arc<RwLock<HashMap<Entity, (Vec2, Vec3)>>>,
+}
+
+impl RaycastProvider for ModRaycast {
+    fn raycast(&self, query: &RaycastQuery) -> Option<Vec3> {
+        let hits = self.hits.read().ok()?;
+        let (position, hit) = hits.get(&query.camera)?;
+        if position.distance(query.screen_position) <= PICK_TOLERANCE {
+            Some(*hit)
+        } else {
+            None
+        }
+    }
+}
+
+/// Registers `bevy_mod_raycast` for [`OrbitRaycastSet`] and makes
+/// [`ModRaycast`] the [`Raycaster`].
+pub struct ModRaycastPlugin;
+
+impl ModRaycastPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn update_sources_system(
+        mut commands: Commands,
+        windows: Res<Windows>,
+        touches: Res<Touches>,
+        mut query: Query<
+            (Entity, &Camera, Option<&mut RayCastSource<OrbitRaycastSet>>),
+            With<OrbitCamera>,
+        >,
+    ) {
+        let touch = touches.iter().next().map(|touch| touch.position());
+        for (entity, camera, source) in query.iter_mut() {
+            let pointer = windows
+                .get(camera.window)
+                .and_then(|window| window.cursor_position())
+                .or(touch);
+            let pointer = match pointer {
+                Some(pointer) => pointer,
+                None => continue,
+            };
+            match source {
+                Some(mut source) => source.cast_method = RayCastMethod::Screenspace(pointer),
+                None => {
+                    let mut source = RayCastSource::<OrbitRaycastSet>::new();
+                    source.cast_method = RayCastMethod::Screenspace(pointer);
+                    commands.entity(entity).insert(source);
+                }
+            }
+        }
+    }
+
+    pub fn cache_hits_system(
+        provider: Res<ModRaycast>,
+        query: Query<(Entity, &RayCastSource<OrbitRaycastSet>), With<OrbitCamera>>,
+    ) {
+        let mut hits = match provider.hits.write() {
+            Ok(hits) => hits,
+            Err(_) => return,
+        };
+        hits.clear();
+        for (entity, source) in query.iter() {
+            if let (RayCastMethod::Screenspace(position), Some((_, hit))) =
+                (&source.cast_method, source.intersect_top())
+            {
+                hits.insert(entity, (*position, hit.position()));
+            }
+        }
+    }
+}
+
+impl Plugin for ModRaycastPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let provider = ModRaycast::default();
+        app.add_plugin(DefaultRaycastingPlugin::<OrbitRaycastSet>::default())
+            .insert_resource(Raycaster::new(provider.clone()))
+            .insert_resource(provider)
+            .add_system_to_stage(CoreStage::PreUpdate, Self::update_sources_system.system())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                Self::cache_hits_system
+                    .system()
+                    .after(RaycastSystem::UpdateRaycast),
+            );
+    }
+}
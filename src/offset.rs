@@ -0,0 +1,69 @@
+//! Effects layered on top of the controlled transform.
+
+use crate::{
+    shown_state, CameraBlendStack, CameraCollision, OrbitCamera, OrbitCameraPlugin,
+    OrbitCameraState, PitchDistance, Trackball,
+};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// An offset, in the camera's own space, that the plugin adds to the
+/// transform it writes, for effects such as screen shake and recoil. The
+/// controlled pose is kept separately, so offsets never build up: write the
+/// offset for the current frame, and set it back to the default to stop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraOffset {
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+impl Default for CameraOffset {
+    fn default() -> Self {
+        CameraOffset {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+impl CameraOffset {
+    pub fn apply(&self, transform: &Transform) -> Transform {
+        Transform {
+            translation: transform.translation + transform.rotation * self.translation,
+            rotation: transform.rotation * self.rotation,
+            scale: transform.scale,
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    /// Puts cameras back where they're controlled to be once their
+    /// [`CameraOffset`] is removed.
+    #[allow(clippy::type_complexity)]
+    pub fn camera_offset_removed_system(
+        removed: RemovedComponents<CameraOffset>,
+        mut query: Query<
+            (
+                &OrbitCamera,
+                &OrbitCameraState,
+                &mut Transform,
+                Option<&Trackball>,
+                Option<&PitchDistance>,
+                Option<&CameraBlendStack>,
+                Option<&CameraCollision>,
+            ),
+            With<Camera>,
+        >,
+    ) {
+        for entity in removed.iter() {
+            if let Ok((camera, state, mut transform, trackball, crane, stack, collision)) =
+                query.get_mut(entity)
+            {
+                if camera.enabled {
+                    let state = shown_state(state, crane, stack, collision);
+                    *transform = camera.controlled_transform(&state, trackball);
+                }
+            }
+        }
+    }
+}
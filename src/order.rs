@@ -0,0 +1,89 @@
+//! The order each frame's input and limits are applied in.
+
+use crate::CameraEvents;
+use bevy::prelude::*;
+
+/// Which of rotating and panning is applied first when a frame has both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MotionOrder {
+    /// In the order the input came in.
+    #[default]
+    AsReceived,
+    RotateFirst,
+    PanFirst,
+}
+
+/// Whether a frame's zoom is applied before or after its rotating and
+/// panning.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ZoomOrder {
+    BeforeMotion,
+    #[default]
+    AfterMotion,
+}
+
+/// When `pitch_range`, `yaw_range` and `distance_range` clamp the target.
+///
+/// Every limit runs in the same order each frame, clamping what the ones
+/// before it left:
+///
+/// 1. A [`CameraRail`](crate::CameraRail) puts the camera on its path.
+/// 2. Walking keeps the center on the ground.
+/// 3. The scene boundary keeps the center and distance inside the scene.
+/// 4. [`Whiskers`](crate::Whiskers) steer around walls.
+/// 5. The frustum limit keeps the scene bounds in view.
+/// 6. `distance_range`, then `yaw_range` and `pitch_range`.
+/// 7. A [`DistancePitchLimit`](crate::DistancePitchLimit) raises the pitch
+///    for the final distance.
+///
+/// Clamping after smoothing moves step 6 to once the frame is smoothed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LimitOrder {
+    /// As input is applied, so the smoothed view eases into a limit and
+    /// never passes it.
+    #[default]
+    BeforeSmoothing,
+    /// Once the frame has been smoothed, so the view runs briefly past a
+    /// limit and springs back.
+    AfterSmoothing,
+}
+
+/// The order the plugin applies each frame's input and limits in. Insert it
+/// before adding the [`OrbitCameraPlugin`], which reads it once to schedule
+/// its systems.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ApplicationOrder {
+    pub motion: MotionOrder,
+    pub zoom: ZoomOrder,
+    pub limits: LimitOrder,
+}
+
+#[derive(SystemLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum DeltaSystem {
+    Motion,
+}
+
+/// The limits, in the order listed on [`LimitOrder`].
+#[derive(SystemLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum LimitSystem {
+    Rail,
+    Walk,
+    SceneBoundary,
+    Whiskers,
+    Frustum,
+    Distance,
+    Angles,
+    DistancePitch,
+}
+
+impl MotionOrder {
+    /// Puts `events` in the order they're applied in.
+    pub(crate) fn sort(&self, events: &mut [&CameraEvents]) {
+        let first: fn(&CameraEvents) -> bool = match self {
+            MotionOrder::AsReceived => return,
+            MotionOrder::RotateFirst => |event| matches!(event, CameraEvents::Orbit(_)),
+            MotionOrder::PanFirst => |event| matches!(event, CameraEvents::Pan(_)),
+        };
+        events.sort_by_key(|event| !first(event));
+    }
+}
@@ -0,0 +1,74 @@
+//! Keeping cameras in place when the world is rebased.
+
+use crate::{
+    CameraBlendStack, CameraHistory, CameraRail, DragPivot, FollowTarget, OrbitCamera,
+    OrbitCameraPlugin, OrbitCameraState, OrbitTransition, PreviousCameraTransform, WalkConstraint,
+};
+use bevy::prelude::*;
+
+/// Sent by games that move the whole world by an offset to keep coordinates
+/// small, such as for precision far from the origin. Everything that was at
+/// `p` is now at `p + offset`, and the plugin moves its cameras, their
+/// targets and the positions it remembers along with it. Send it in the frame
+/// the world moves, before
+/// [`OrbitCameraSystem::Input`](crate::OrbitCameraSystem::Input).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorldOriginShifted(pub Vec3);
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn world_origin_shift_system(
+        mut events: EventReader<WorldOriginShifted>,
+        mut query: Query<(
+            &mut OrbitCamera,
+            Option<&mut OrbitCameraState>,
+            Option<&mut FollowTarget>,
+            Option<&mut OrbitTransition>,
+            Option<&mut WalkConstraint>,
+            Option<&mut PreviousCameraTransform>,
+            Option<&mut DragPivot>,
+            Option<&mut CameraHistory>,
+            Option<&mut CameraBlendStack>,
+            Option<&mut CameraRail>,
+        )>,
+    ) {
+        let offset = events
+            .iter()
+            .fold(Vec3::ZERO, |offset, event| offset + event.0);
+        if offset == Vec3::ZERO {
+            return;
+        }
+        for (mut camera, state, follow, transition, walk, previous, pivot, history, stack, rail) in
+            query.iter_mut()
+        {
+            camera.center += offset;
+            if let Some(mut state) = state {
+                state.center += offset;
+            }
+            if let Some(mut follow) = follow {
+                follow.shift_origin(offset);
+            }
+            if let Some(mut transition) = transition {
+                transition.shift_origin(offset);
+            }
+            if let Some(mut walk) = walk {
+                walk.shift_origin(offset);
+            }
+            if let Some(mut previous) = previous {
+                previous.shift_origin(offset);
+            }
+            if let Some(mut pivot) = pivot {
+                pivot.0 += offset;
+            }
+            if let Some(mut history) = history {
+                history.shift_origin(offset);
+            }
+            if let Some(mut stack) = stack {
+                stack.shift_origin(offset);
+            }
+            if let Some(mut rail) = rail {
+                rail.shift_origin(offset);
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Keyframed camera animation.
+
+use crate::{CameraSuspended, OrbitCamera, OrbitCameraPlugin, OrbitCameraState, OrbitPose};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyframe {
+    /// Seconds from the start of the path.
+    pub time: f32,
+    pub pose: OrbitPose,
+}
+
+/// A camera animation through [`OrbitPose`]s at given times, played back by
+/// a [`CameraPathPlayer`].
+#[derive(Clone, Debug, Default, PartialEq, TypeUuid)]
+#[uuid = "41805195-afd8-4786-b860-441942bbe448"]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+}
+
+impl CameraPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a keyframe, keeping them in time order.
+    pub fn add(&mut self, time: f32, pose: OrbitPose) {
+        let index = self.keyframes.partition_point(|key| key.time <= time);
+        self.keyframes.insert(index, Keyframe { time, pose });
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    pub fn clear(&mut self) {
+        self.keyframes.clear();
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |key| key.time)
+    }
+
+    /// The pose at `time`, held at the first and last keyframes beyond the
+    /// ends of the path.
+    pub fn evaluate(&self, time: f32) -> Option<OrbitPose> {
+        let next = self.keyframes.partition_point(|key| key.time <= time);
+        if next == 0 {
+            return self.keyframes.first().map(|key| key.pose);
+        }
+        let from = self.keyframes[next - 1];
+        let to = match self.keyframes.get(next) {
+            Some(to) => *to,
+            None => return Some(from.pose),
+        };
+        let span = to.time - from.time;
+        let t = if span > 0.0 {
+            (time - from.time) / span
+        } else {
+            1.0
+        };
+        let t = t * t * (3.0 - 2.0 * t);
+        let distance = if from.pose.distance > 0.0 && to.pose.distance > 0.0 {
+            from.pose.distance * (to.pose.distance / from.pose.distance).powf(t)
+        } else {
+            from.pose.distance + (to.pose.distance - from.pose.distance) * t
+        };
+        Some(OrbitPose {
+            x: from.pose.x + (to.pose.x - from.pose.x) * t,
+            y: from.pose.y + (to.pose.y - from.pose.y) * t,
+            distance,
+            center: from.pose.center.lerp(to.pose.center, t),
+        })
+    }
+
+    /// Indices of the keyframes passed going from `from` to `to`, in the
+    /// order they were passed.
+    pub fn keyframes_between(&self, from: f32, to: f32) -> Vec<usize> {
+        let mut crossed: Vec<usize> = self
+            .keyframes
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| {
+                if to >= from {
+                    key.time > from && key.time <= to
+                } else {
+                    key.time < from && key.time >= to
+                }
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if to < from {
+            crossed.reverse();
+        }
+        crossed
+    }
+}
+
+/// Plays a [`CameraPath`] on the camera on the same entity. Seeking snaps the
+/// camera to the path, for scrubbing on a timeline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraPathPlayer {
+    pub path: Handle<CameraPath>,
+    pub playing: bool,
+    pub speed: f32,
+    pub looping: bool,
+    time: f32,
+    evaluated: Option<f32>,
+}
+
+impl CameraPathPlayer {
+    pub fn new(path: Handle<CameraPath>) -> Self {
+        CameraPathPlayer {
+            path,
+            playing: false,
+            speed: 1.0,
+            looping: false,
+            time: 0.0,
+            evaluated: None,
+        }
+    }
+
+    /// A player that starts right away and plays the path once, for guided
+    /// tours and cinematics.
+    pub fn tour(path: Handle<CameraPath>) -> Self {
+        CameraPathPlayer {
+            playing: true,
+            ..CameraPathPlayer::new(path)
+        }
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Jumps to `time` seconds into the path.
+    pub fn seek(&mut self, time: f32) {
+        self.time = time.max(0.0);
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+}
+
+/// Sent when playback or scrubbing passes a keyframe of a [`CameraPath`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyframeCrossed {
+    pub camera: Entity,
+    pub index: usize,
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn camera_path_system(
+        time: Res<Time>,
+        paths: Res<Assets<CameraPath>>,
+        mut events: EventWriter<KeyframeCrossed>,
+        mut query: Query<
+            (
+                Entity,
+                &mut OrbitCamera,
+                Option<&mut OrbitCameraState>,
+                &mut CameraPathPlayer,
+            ),
+            Without<CameraSuspended>,
+        >,
+    ) {
+        for (entity, mut camera, state, mut player) in query.iter_mut() {
+            let path = match paths.get(&player.path) {
+                Some(path) => path,
+                None => continue,
+            };
+            let duration = path.duration();
+            if player.playing {
+                player.time += time.delta_seconds() * player.speed;
+                if player.time >= duration || player.time < 0.0 {
+                    if player.looping && duration > 0.0 {
+                        player.time = player.time.rem_euclid(duration);
+                        player.evaluated = None;
+                    } else {
+                        player.time = player.time.max(0.0).min(duration);
+                        player.playing = false;
+                    }
+                }
+            }
+            if player.evaluated == Some(player.time) {
+                continue;
+            }
+            if let Some(from) = player.evaluated {
+                for index in path.keyframes_between(from, player.time) {
+                    events.send(KeyframeCrossed {
+                        camera: entity,
+                        index,
+                    });
+                }
+            }
+            player.evaluated = Some(player.time);
+            if let Some(pose) = path.evaluate(player.time) {
+                pose.apply(&mut camera);
+                if let Some(mut state) = state {
+                    *state = OrbitCameraState::from(&*camera);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pose(x: f32, distance: f32) -> OrbitPose {
+        OrbitPose {
+            x,
+            y: 1.0,
+            distance,
+            center: Vec3::new(x, 0.0, 0.0),
+        }
+    }
+
+    fn path() -> CameraPath {
+        let mut path = CameraPath::new();
+        path.add(2.0, pose(1.0, 8.0));
+        path.add(1.0, pose(0.0, 2.0));
+        path
+    }
+
+    #[test]
+    fn evaluates_nothing_without_keyframes() {
+        assert_eq!(CameraPath::new().evaluate(0.0), None);
+        assert_eq!(CameraPath::new().duration(), 0.0);
+    }
+
+    #[test]
+    fn holds_the_ends() {
+        let path = path();
+        assert_eq!(path.duration(), 2.0);
+        assert_eq!(path.evaluate(-1.0), Some(pose(0.0, 2.0)));
+        assert_eq!(path.evaluate(1.0), Some(pose(0.0, 2.0)));
+        assert_eq!(path.evaluate(2.0), Some(pose(1.0, 8.0)));
+        assert_eq!(path.evaluate(f32::INFINITY), Some(pose(1.0, 8.0)));
+    }
+
+    #[test]
+    fn eases_between_keyframes() {
+        let halfway = path().evaluate(1.5).unwrap();
+        assert!((halfway.x - 0.5).abs() < 1e-6);
+        assert!((halfway.center.x - 0.5).abs() < 1e-6);
+        // Zooms geometrically.
+        assert!((halfway.distance - 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn jumps_at_keyframes_sharing_a_time() {
+        let mut path = path();
+        path.add(1.0, pose(0.5, 4.0));
+        assert_eq!(path.evaluate(1.0), Some(pose(0.5, 4.0)));
+        assert_eq!(path.evaluate(0.5), Some(pose(0.0, 2.0)));
+    }
+
+    #[test]
+    fn finds_crossed_keyframes() {
+        let path = path();
+        assert_eq!(path.keyframes_between(0.0, 1.0), vec![0]);
+        assert_eq!(path.keyframes_between(1.0, 3.0), vec![1]);
+        assert_eq!(path.keyframes_between(3.0, 0.0), vec![1, 0]);
+        assert!(path.keyframes_between(1.2, 1.8).is_empty());
+    }
+}
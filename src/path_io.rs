@@ -0,0 +1,659 @@
+//! Exchanging camera paths with other tools.
+
+use crate::{CameraMode, CameraPath, Migrations, OrbitCamera, OrbitCameraState, OrbitPose};
+use bevy::prelude::*;
+use serde_json::{json, Value};
+use std::fmt::{self, Write};
+
+/// The version written into JSON camera paths.
+const PATH_JSON_VERSION: u32 = 1;
+
+fn pose_transform(pose: &OrbitPose) -> Transform {
+    let mut camera = OrbitCamera::default();
+    pose.apply(&mut camera);
+    OrbitCameraState::from(&camera).transform(CameraMode::Orbit)
+}
+
+impl CameraPath {
+    /// One keyframe per line, as `time,x,y,distance,center_x,center_y,center_z`
+    /// after a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time,x,y,distance,center_x,center_y,center_z\n");
+        for key in self.keyframes() {
+            let pose = key.pose;
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                key.time,
+                pose.x,
+                pose.y,
+                pose.distance,
+                pose.center.x,
+                pose.center.y,
+                pose.center.z
+            );
+        }
+        csv
+    }
+
+    /// The keyframes as versioned JSON.
+    pub fn to_json(&self) -> String {
+        let keyframes: Vec<_> = self
+            .keyframes()
+            .iter()
+            .map(|key| {
+                json!({
+                    "time": key.time,
+                    "x": key.pose.x,
+                    "y": key.pose.y,
+                    "distance": key.pose.distance,
+                    "center": [key.pose.center.x, key.pose.center.y, key.pose.center.z],
+                })
+            })
+            .collect();
+        json!({ "version": PATH_JSON_VERSION, "keyframes": keyframes }).to_string()
+    }
+
+    /// A self-contained glTF 2.0 document with a perspective camera node
+    /// animated along the path, sampled `samples_per_second` times a second
+    /// so the orbit's curves survive linear interpolation. `yfov` is the
+    /// vertical field of view in radians. An empty path exports the camera
+    /// without an animation.
+    pub fn to_gltf(&self, samples_per_second: f32, yfov: f32) -> String {
+        let duration = self.duration();
+        let mut times: Vec<f32> = if samples_per_second > 0.0 {
+            (0..)
+                .map(|i| i as f32 / samples_per_second)
+                .take_while(|time| *time < duration)
+                .chain(std::iter::once(duration))
+                .collect()
+        } else {
+            self.keyframes().iter().map(|key| key.time).collect()
+        };
+        // glTF needs strictly increasing times.
+        times.dedup();
+        let transforms: Vec<_> = times
+            .iter()
+            .filter_map(|time| self.evaluate(*time))
+            .map(|pose| pose_transform(&pose))
+            .collect();
+        let count = transforms.len();
+
+        let mut bytes = Vec::with_capacity(count * (4 + 12 + 16));
+        for time in &times[..count] {
+            bytes.extend_from_slice(&time.to_le_bytes());
+        }
+        for transform in &transforms {
+            let translation: [f32; 3] = transform.translation.into();
+            for value in translation.iter() {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        for transform in &transforms {
+            let rotation: [f32; 4] = transform.rotation.into();
+            for value in rotation.iter() {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let (first, last) = match (times.first(), times[..count].last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => (0.0, 0.0),
+        };
+        let initial = transforms.first().copied().unwrap_or_default();
+        let initial_translation: [f32; 3] = initial.translation.into();
+        let initial_rotation: [f32; 4] = initial.rotation.into();
+
+        let mut document = json!({
+            "asset": { "version": "2.0", "generator": "bevy-orbit-controls" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [{
+                "name": "OrbitCamera",
+                "camera": 0,
+                "translation": initial_translation,
+                "rotation": initial_rotation,
+            }],
+            "cameras": [{
+                "type": "perspective",
+                "perspective": { "yfov": yfov, "znear": 0.1 },
+            }],
+        });
+        if count == 0 {
+            return document.to_string();
+        }
+        let animation = json!({
+            "buffers": [{
+                "byteLength": bytes.len(),
+                "uri": format!("data:application/octet-stream;base64,{}", base64::encode(&bytes)),
+            }],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": count * 4 },
+                { "buffer": 0, "byteOffset": count * 4, "byteLength": count * 12 },
+                { "buffer": 0, "byteOffset": count * 16, "byteLength": count * 16 },
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5126, "count": count, "type": "SCALAR",
+                    "min": [first], "max": [last],
+                },
+                { "bufferView": 1, "componentType": 5126, "count": count, "type": "VEC3" },
+                { "bufferView": 2, "componentType": 5126, "count": count, "type": "VEC4" },
+            ],
+            "animations": [{
+                "name": "CameraPath",
+                "samplers": [
+                    { "input": 0, "output": 1, "interpolation": "LINEAR" },
+                    { "input": 0, "output": 2, "interpolation": "LINEAR" },
+                ],
+                "channels": [
+                    { "sampler": 0, "target": { "node": 0, "path": "translation" } },
+                    { "sampler": 1, "target": { "node": 0, "path": "rotation" } },
+                ],
+            }],
+        });
+        if let (Some(document), Value::Object(animation)) = (document.as_object_mut(), animation) {
+            document.extend(animation);
+        }
+        document.to_string()
+    }
+}
+
+/// Upgrades JSON camera paths written by older versions.
+fn path_json_migrations() -> Migrations<Value> {
+    Migrations::new(PATH_JSON_VERSION)
+}
+
+/// Why a camera animation couldn't be imported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathImportError {
+    Malformed(&'static str),
+    /// The JSON was written by a newer version of the format.
+    UnsupportedVersion,
+}
+
+impl fmt::Display for PathImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathImportError::Malformed(reason) => write!(f, "malformed camera path: {}", reason),
+            PathImportError::UnsupportedVersion => f.write_str("unsupported camera path version"),
+        }
+    }
+}
+
+impl std::error::Error for PathImportError {}
+
+fn malformed(reason: &'static str) -> PathImportError {
+    PathImportError::Malformed(reason)
+}
+
+/// The pose of a camera at `transform`, orbiting a center `distance` ahead.
+fn transform_pose(transform: Transform, distance: f32) -> OrbitPose {
+    let forward = transform.rotation * -Vec3::Z;
+    let offset = -forward.normalize();
+    OrbitPose {
+        x: (-offset.x).atan2(-offset.z),
+        y: offset.y.clamp(-1.0, 1.0).acos(),
+        distance,
+        center: transform.translation + forward * distance,
+    }
+}
+
+struct Channel {
+    times: Vec<f32>,
+    /// One value per time; cubic spline tangents are dropped when reading.
+    values: Vec<Vec<f32>>,
+    /// Holds each value until the next key instead of interpolating.
+    step: bool,
+}
+
+impl Channel {
+    /// Reads the keys of `sampler`, taking the value element of each cubic
+    /// spline key.
+    fn read(
+        document: &Value,
+        buffers: &[Vec<u8>],
+        sampler: &Value,
+        components: usize,
+    ) -> Result<Channel, PathImportError> {
+        let input = sampler["input"]
+            .as_u64()
+            .ok_or_else(|| malformed("sampler without input"))?;
+        let output = sampler["output"]
+            .as_u64()
+            .ok_or_else(|| malformed("sampler without output"))?;
+        let times: Vec<f32> = read_floats(document, buffers, input as usize, 1)?
+            .into_iter()
+            .map(|time| time[0])
+            .collect();
+        let values = read_floats(document, buffers, output as usize, components)?;
+        let (values, step) = match sampler["interpolation"].as_str().unwrap_or("LINEAR") {
+            "LINEAR" => (values, false),
+            "STEP" => (values, true),
+            "CUBICSPLINE" => {
+                if values.len() != times.len() * 3 {
+                    return Err(malformed("sampler output doesn't match its input"));
+                }
+                (values.into_iter().skip(1).step_by(3).collect(), false)
+            }
+            _ => return Err(malformed("unknown sampler interpolation")),
+        };
+        if values.len() != times.len() {
+            return Err(malformed("sampler output doesn't match its input"));
+        }
+        Ok(Channel {
+            times,
+            values,
+            step,
+        })
+    }
+
+    fn sample(&self, time: f32) -> Option<(Vec<f32>, Vec<f32>, f32)> {
+        let next = self.times.iter().position(|t| *t > time);
+        let (from, to) = match next {
+            Some(0) => (0, 0),
+            Some(next) => (next - 1, next),
+            None => (self.times.len().checked_sub(1)?, self.times.len() - 1),
+        };
+        let span = self.times[to] - self.times[from];
+        let t = if span > 0.0 && !self.step {
+            (time - self.times[from]) / span
+        } else {
+            0.0
+        };
+        Some((self.values[from].clone(), self.values[to].clone(), t))
+    }
+}
+
+fn read_floats(
+    document: &Value,
+    buffers: &[Vec<u8>],
+    accessor: usize,
+    components: usize,
+) -> Result<Vec<Vec<f32>>, PathImportError> {
+    let accessor = &document["accessors"][accessor];
+    if accessor["componentType"].as_u64() != Some(5126) {
+        return Err(malformed("only float accessors are supported"));
+    }
+    let count = accessor["count"]
+        .as_u64()
+        .ok_or_else(|| malformed("accessor without count"))? as usize;
+    let view = &document["bufferViews"][accessor["bufferView"]
+        .as_u64()
+        .ok_or_else(|| malformed("accessor without buffer view"))?
+        as usize];
+    let buffer = buffers
+        .get(view["buffer"].as_u64().unwrap_or(0) as usize)
+        .ok_or_else(|| malformed("missing buffer"))?;
+    let offset = view["byteOffset"]
+        .as_u64()
+        .unwrap_or(0)
+        .checked_add(accessor["byteOffset"].as_u64().unwrap_or(0))
+        .ok_or_else(|| malformed("accessor runs past its buffer"))? as usize;
+    let stride = view["byteStride"]
+        .as_u64()
+        .map_or(components * 4, |stride| stride as usize);
+    (0..count)
+        .map(|i| {
+            (0..components)
+                .map(|c| {
+                    let past = || malformed("accessor runs past its buffer");
+                    let start = i
+                        .checked_mul(stride)
+                        .and_then(|row| row.checked_add(c * 4))
+                        .and_then(|element| element.checked_add(offset))
+                        .ok_or_else(past)?;
+                    let bytes = buffer
+                        .get(start..start.checked_add(4).ok_or_else(past)?)
+                        .ok_or_else(past)?;
+                    Ok(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl CameraPath {
+    /// Reads keyframes written by [`CameraPath::to_json`], including by older
+    /// versions of the crate.
+    pub fn from_json(json: &str) -> Result<CameraPath, PathImportError> {
+        let document: Value = serde_json::from_str(json).map_err(|_| malformed("invalid JSON"))?;
+        let version = document["version"]
+            .as_u64()
+            .ok_or_else(|| malformed("missing version"))?;
+        let document = path_json_migrations()
+            .migrate(version.min(u32::MAX as u64) as u32, document)
+            .map_err(|_| PathImportError::UnsupportedVersion)?;
+        let number = |value: &Value| value.as_f64().map(|value| value as f32);
+        let mut path = CameraPath::new();
+        for key in document["keyframes"]
+            .as_array()
+            .ok_or_else(|| malformed("missing keyframes"))?
+        {
+            let field = |name| number(&key[name]).ok_or_else(|| malformed("incomplete keyframe"));
+            let center = |i: usize| {
+                number(&key["center"][i]).ok_or_else(|| malformed("incomplete keyframe"))
+            };
+            path.add(
+                field("time")?,
+                OrbitPose {
+                    x: field("x")?,
+                    y: field("y")?,
+                    distance: field("distance")?,
+                    center: Vec3::new(center(0)?, center(1)?, center(2)?),
+                },
+            );
+        }
+        Ok(path)
+    }
+
+    /// Reads the animation of the first camera node in a glTF document. Buffers
+    /// may be embedded as data URIs; otherwise `binary` supplies the first
+    /// buffer, as the `.bin` file or GLB chunk. The camera orbits a center
+    /// `distance` in front of it. Cubic spline tangents are ignored, so the
+    /// camera still passes through every key but moves linearly between them.
+    pub fn from_gltf(
+        json: &str,
+        binary: Option<&[u8]>,
+        distance: f32,
+    ) -> Result<CameraPath, PathImportError> {
+        let document: Value = serde_json::from_str(json).map_err(|_| malformed("invalid JSON"))?;
+        let buffers = document["buffers"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, buffer)| match buffer["uri"].as_str() {
+                Some(uri) if uri.starts_with("data:") => {
+                    let data = uri
+                        .split(',')
+                        .nth(1)
+                        .ok_or_else(|| malformed("invalid data URI"))?;
+                    base64::decode(data).map_err(|_| malformed("invalid base64 in data URI"))
+                }
+                _ if index == 0 => binary
+                    .map(|binary| binary.to_vec())
+                    .ok_or_else(|| malformed("external buffer not supplied")),
+                _ => Err(malformed("only the first buffer may be external")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let node = document["nodes"]
+            .as_array()
+            .and_then(|nodes| nodes.iter().position(|node| node.get("camera").is_some()))
+            .ok_or_else(|| malformed("no camera node"))?;
+        let mut translation = None;
+        let mut rotation = None;
+        for animation in document["animations"].as_array().into_iter().flatten() {
+            for channel in animation["channels"].as_array().into_iter().flatten() {
+                if channel["target"]["node"].as_u64() != Some(node as u64) {
+                    continue;
+                }
+                let sampler =
+                    &animation["samplers"][channel["sampler"].as_u64().unwrap_or(0) as usize];
+                let (slot, components) = match channel["target"]["path"].as_str() {
+                    Some("translation") => (&mut translation, 3),
+                    Some("rotation") => (&mut rotation, 4),
+                    _ => continue,
+                };
+                *slot = Some(Channel::read(&document, &buffers, sampler, components)?);
+            }
+        }
+
+        let rest = &document["nodes"][node];
+        let rest_value = |name: &str, i: usize, default: f32| {
+            rest[name][i].as_f64().map_or(default, |value| value as f32)
+        };
+        let rest_translation = Vec3::new(
+            rest_value("translation", 0, 0.0),
+            rest_value("translation", 1, 0.0),
+            rest_value("translation", 2, 0.0),
+        );
+        let rest_rotation = Quat::from_xyzw(
+            rest_value("rotation", 0, 0.0),
+            rest_value("rotation", 1, 0.0),
+            rest_value("rotation", 2, 0.0),
+            rest_value("rotation", 3, 1.0),
+        );
+
+        let mut times: Vec<f32> = translation
+            .iter()
+            .chain(rotation.iter())
+            .flat_map(|channel| channel.times.iter().copied())
+            .collect();
+        if times.is_empty() {
+            return Err(malformed("the camera node isn't animated"));
+        }
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        times.dedup();
+
+        let mut path = CameraPath::new();
+        let mut last_x: Option<f32> = None;
+        for time in times {
+            let position = match translation.as_ref().and_then(|c| c.sample(time)) {
+                Some((from, to, t)) => {
+                    Vec3::new(from[0], from[1], from[2]).lerp(Vec3::new(to[0], to[1], to[2]), t)
+                }
+                None => rest_translation,
+            };
+            let orientation = match rotation.as_ref().and_then(|c| c.sample(time)) {
+                Some((from, to, t)) => Quat::from_xyzw(from[0], from[1], from[2], from[3])
+                    .slerp(Quat::from_xyzw(to[0], to[1], to[2], to[3]), t),
+                None => rest_rotation,
+            };
+            let mut pose = transform_pose(
+                Transform {
+                    translation: position,
+                    rotation: orientation,
+                    ..Default::default()
+                },
+                distance,
+            );
+            // Keep turning the short way round between keyframes.
+            if let Some(last_x) = last_x {
+                let turns = ((last_x - pose.x) / std::f32::consts::TAU).round();
+                pose.x += turns * std::f32::consts::TAU;
+            }
+            last_x = Some(pose.x);
+            path.add(time, pose);
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path() -> CameraPath {
+        let mut path = CameraPath::new();
+        path.add(
+            0.0,
+            OrbitPose {
+                x: 0.5,
+                y: 1.0,
+                distance: 5.0,
+                center: Vec3::new(1.0, 0.0, -2.0),
+            },
+        );
+        path.add(
+            1.5,
+            OrbitPose {
+                x: 1.25,
+                y: 0.75,
+                distance: 5.0,
+                center: Vec3::new(0.0, 2.0, 0.0),
+            },
+        );
+        path
+    }
+
+    fn assert_close(a: &OrbitPose, b: &OrbitPose) {
+        assert!((a.x - b.x).abs() < 1e-3, "{:?} != {:?}", a, b);
+        assert!((a.y - b.y).abs() < 1e-3, "{:?} != {:?}", a, b);
+        assert!((a.distance - b.distance).abs() < 1e-3, "{:?} != {:?}", a, b);
+        assert!(a.center.distance(b.center) < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    fn edit_gltf(gltf: &str, edit: impl FnOnce(&mut Value)) -> String {
+        let mut document: Value = serde_json::from_str(gltf).unwrap();
+        edit(&mut document);
+        document.to_string()
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let json = path().to_json();
+        assert_eq!(CameraPath::from_json(&json), Ok(path()));
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert_eq!(
+            CameraPath::from_json("{"),
+            Err(PathImportError::Malformed("invalid JSON"))
+        );
+        assert_eq!(
+            CameraPath::from_json(r#"{"version":2,"keyframes":[]}"#),
+            Err(PathImportError::UnsupportedVersion)
+        );
+        assert_eq!(
+            CameraPath::from_json(r#"{"version":1,"keyframes":[{"time":0}]}"#),
+            Err(PathImportError::Malformed("incomplete keyframe"))
+        );
+    }
+
+    #[test]
+    fn writes_csv() {
+        let csv = path().to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("time,x,y,distance,center_x,center_y,center_z")
+        );
+        assert_eq!(lines.next(), Some("0,0.5,1,5,1,0,-2"));
+        assert_eq!(lines.count(), 1);
+    }
+
+    #[test]
+    fn round_trips_gltf_keyframes() {
+        let gltf = path().to_gltf(0.0, 0.8);
+        let imported = CameraPath::from_gltf(&gltf, None, 5.0).unwrap();
+        assert_eq!(imported.keyframes().len(), 2);
+        for (imported, key) in imported.keyframes().iter().zip(path().keyframes()) {
+            assert_eq!(imported.time, key.time);
+            assert_close(&imported.pose, &key.pose);
+        }
+    }
+
+    #[test]
+    fn samples_gltf_up_to_the_duration() {
+        let mut path = path();
+        path.clear();
+        path.add(0.0, OrbitPose::from(&OrbitCamera::default()));
+        path.add(0.3, OrbitPose::from(&OrbitCamera::default()));
+        let imported = CameraPath::from_gltf(&path.to_gltf(10.0, 0.8), None, 5.0).unwrap();
+        let times: Vec<f32> = imported.keyframes().iter().map(|key| key.time).collect();
+        assert_eq!(times.len(), 4);
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(times.last(), Some(&0.3));
+    }
+
+    #[test]
+    fn exports_empty_paths_without_an_animation() {
+        let gltf = CameraPath::new().to_gltf(30.0, 0.8);
+        let document: Value = serde_json::from_str(&gltf).unwrap();
+        assert!(document.get("accessors").is_none());
+        assert!(document.get("animations").is_none());
+        assert_eq!(
+            CameraPath::from_gltf(&gltf, None, 5.0),
+            Err(PathImportError::Malformed("the camera node isn't animated"))
+        );
+    }
+
+    #[test]
+    fn rejects_outputs_that_dont_match_their_input() {
+        let short = edit_gltf(&path().to_gltf(0.0, 0.8), |document| {
+            document["accessors"][1]["count"] = json!(1);
+        });
+        assert_eq!(
+            CameraPath::from_gltf(&short, None, 5.0),
+            Err(PathImportError::Malformed(
+                "sampler output doesn't match its input"
+            ))
+        );
+        let cubic = edit_gltf(&path().to_gltf(0.0, 0.8), |document| {
+            document["animations"][0]["samplers"][0]["interpolation"] = json!("CUBICSPLINE");
+        });
+        assert!(CameraPath::from_gltf(&cubic, None, 5.0).is_err());
+    }
+
+    #[test]
+    fn reads_the_values_of_cubic_spline_keys() {
+        let linear = path().to_gltf(0.0, 0.8);
+        let cubic = edit_gltf(&linear, |document| {
+            // Repeat each translation as its own in- and out-tangent.
+            let bytes = base64::decode(
+                document["buffers"][0]["uri"]
+                    .as_str()
+                    .unwrap()
+                    .split(',')
+                    .nth(1)
+                    .unwrap(),
+            )
+            .unwrap();
+            let mut tripled = bytes.clone();
+            let translations = &bytes[8..32];
+            tripled.truncate(8);
+            for key in translations.chunks(12) {
+                for _ in 0..3 {
+                    tripled.extend_from_slice(key);
+                }
+            }
+            let offset = tripled.len();
+            tripled.extend_from_slice(&bytes[32..]);
+            document["buffers"][0] = json!({
+                "byteLength": tripled.len(),
+                "uri": format!("data:application/octet-stream;base64,{}", base64::encode(&tripled)),
+            });
+            document["bufferViews"][1]["byteLength"] = json!(72);
+            document["bufferViews"][2]["byteOffset"] = json!(offset);
+            document["accessors"][1]["count"] = json!(6);
+            document["animations"][0]["samplers"][0]["interpolation"] = json!("CUBICSPLINE");
+        });
+        assert_eq!(
+            CameraPath::from_gltf(&cubic, None, 5.0),
+            CameraPath::from_gltf(&linear, None, 5.0)
+        );
+    }
+
+    #[test]
+    fn holds_step_keys() {
+        let step = edit_gltf(&path().to_gltf(0.0, 0.8), |document| {
+            for sampler in document["animations"][0]["samplers"]
+                .as_array_mut()
+                .unwrap()
+            {
+                sampler["interpolation"] = json!("STEP");
+            }
+        });
+        let imported = CameraPath::from_gltf(&step, None, 5.0).unwrap();
+        assert_close(&imported.keyframes()[0].pose, &path().keyframes()[0].pose);
+        let channel = Channel {
+            times: vec![0.0, 1.0],
+            values: vec![vec![0.0], vec![1.0]],
+            step: true,
+        };
+        assert_eq!(channel.sample(0.5), Some((vec![0.0], vec![1.0], 0.0)));
+    }
+
+    #[test]
+    fn rejects_accessors_past_their_buffer() {
+        let huge = edit_gltf(&path().to_gltf(0.0, 0.8), |document| {
+            document["bufferViews"][1]["byteOffset"] = json!(u64::MAX);
+        });
+        assert_eq!(
+            CameraPath::from_gltf(&huge, None, 5.0),
+            Err(PathImportError::Malformed("accessor runs past its buffer"))
+        );
+    }
+}
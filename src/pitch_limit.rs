@@ -0,0 +1,58 @@
+//! Pitch limits that change with distance.
+
+use crate::{CameraFeedback, CameraLimit, CameraMode, OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+
+/// Limits how low an orbiting camera on the same entity may look from,
+/// depending on its distance from the center, for example to stay above the
+/// horizon when zoomed out over terrain but not when close. The function maps
+/// `distance` to the largest allowed [`OrbitCamera::y`], where
+/// `FRAC_PI_2` is level with the center.
+pub struct DistancePitchLimit {
+    pub lowest: Box<dyn Fn(f32) -> f32 + Send + Sync>,
+}
+
+impl DistancePitchLimit {
+    pub fn new(lowest: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        DistancePitchLimit {
+            lowest: Box::new(lowest),
+        }
+    }
+
+    /// Allows down to `near_lowest` at `near` distance and `far_lowest` at
+    /// `far`, blending linearly in between.
+    pub fn between(near: f32, near_lowest: f32, far: f32, far_lowest: f32) -> Self {
+        DistancePitchLimit::new(move |distance| {
+            let t = if far > near {
+                ((distance - near) / (far - near)).clamp(0.0, 1.0)
+            } else if distance < far {
+                0.0
+            } else {
+                1.0
+            };
+            near_lowest + (far_lowest - near_lowest) * t
+        })
+    }
+
+    pub fn lowest(&self, distance: f32) -> f32 {
+        (self.lowest)(distance)
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn distance_pitch_limit_system(
+        mut feedback: EventWriter<CameraFeedback>,
+        mut query: Query<(Entity, &mut OrbitCamera, &DistancePitchLimit)>,
+    ) {
+        for (entity, mut camera, limit) in query.iter_mut() {
+            if camera.mode != CameraMode::Orbit {
+                continue;
+            }
+            let lowest = limit.lowest(camera.distance);
+            if camera.y > lowest {
+                camera.y = lowest.max(*camera.pitch_range.start());
+                feedback.send(CameraFeedback::limit(entity, CameraLimit::Pitch));
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Rotating about the point under the cursor.
+
+use crate::ray::pick;
+use crate::{ControlScheme, OrbitCamera, OrbitCameraPlugin, Raycaster};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// What dragging with the rotate button turns the camera about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RotationPivot {
+    /// The orbit center.
+    Center,
+    /// The point under the cursor when the drag started, which stays under
+    /// the cursor while the orbit center moves around it.
+    Cursor,
+}
+
+/// The point a drag is rotating about, present while rotating with
+/// [`RotationPivot::Cursor`] and the [`Raycaster`] found something.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragPivot(pub Vec3);
+
+/// Moves `center` as if the whole orbit about `up` turned rigidly about
+/// `pivot` when the angles went from `from` to `to`.
+pub(crate) fn rotate_about(
+    center: Vec3,
+    pivot: Vec3,
+    up: Vec3,
+    from: (f32, f32),
+    to: (f32, f32),
+) -> Vec3 {
+    let basis = up_rotation(up);
+    let rotation = basis
+        * orbit_rotation(to.0, to.1)
+        * orbit_rotation(from.0, from.1).inverse()
+        * basis.inverse();
+    pivot + rotation * (center - pivot)
+}
+
+/// The orientation of the camera for angles measured about +Y.
+pub(crate) fn orbit_rotation(x: f32, y: f32) -> Quat {
+    Quat::from_axis_angle(Vec3::Y, x) * Quat::from_axis_angle(-Vec3::X, y)
+}
+
+/// Turns +Y onto `up`, mapping angles about +Y to angles about `up`.
+pub(crate) fn up_rotation(up: Vec3) -> Quat {
+    let up = up.normalize();
+    if up.is_finite() {
+        Quat::from_rotation_arc(Vec3::Y, up)
+    } else {
+        Quat::IDENTITY
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn drag_pivot_system(
+        mut commands: Commands,
+        mouse_button_input: Res<Input<MouseButton>>,
+        keyboard_input: Res<Input<KeyCode>>,
+        windows: Res<Windows>,
+        raycaster: Res<Raycaster>,
+        query: Query<(
+            Entity,
+            &OrbitCamera,
+            &Camera,
+            &GlobalTransform,
+            Option<&DragPivot>,
+        )>,
+    ) {
+        for (entity, orbit, camera, transform, pivot) in query.iter() {
+            if orbit.rotation_pivot != RotationPivot::Cursor
+                || orbit.control_scheme != ControlScheme::Standard
+                || !orbit.drags(&mouse_button_input, &keyboard_input).rotate
+            {
+                if pivot.is_some() {
+                    commands.entity(entity).remove::<DragPivot>();
+                }
+                continue;
+            }
+            if !mouse_button_input.just_pressed(orbit.rotate_binding.button) {
+                continue;
+            }
+            let cursor = windows
+                .get(camera.window)
+                .and_then(|window| window.cursor_position());
+            let point = cursor.and_then(|cursor| {
+                pick(
+                    &raycaster, entity, orbit, camera, transform, &windows, cursor,
+                )
+            });
+            match point {
+                Some(point) => {
+                    commands.entity(entity).insert(DragPivot(point));
+                }
+                None if pivot.is_some() => {
+                    commands.entity(entity).remove::<DragPivot>();
+                }
+                None => {}
+            }
+        }
+    }
+}
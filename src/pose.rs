@@ -0,0 +1,222 @@
+use crate::{Conventions, Migrations, OrbitCamera, OrbitTransition};
+use bevy::prelude::*;
+use std::fmt;
+
+const POSE_STRING_VERSION: u32 = 1;
+
+/// Upgrades the values of pose strings written by older versions.
+fn pose_string_migrations() -> Migrations<Vec<f32>> {
+    Migrations::new(POSE_STRING_VERSION)
+}
+
+const DEGREES: Conventions = Conventions {
+    degrees: true,
+    ..Conventions::INTERNAL
+};
+
+/// A snapshot of where an [`OrbitCamera`] is looking from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitPose {
+    pub x: f32,
+    pub y: f32,
+    pub distance: f32,
+    pub center: Vec3,
+}
+
+impl OrbitPose {
+    pub fn apply(&self, camera: &mut OrbitCamera) {
+        camera.x = self.x;
+        camera.y = self.y;
+        camera.distance = self.distance;
+        camera.center = self.center;
+    }
+
+    /// A transition moving the camera to this pose over `duration` seconds.
+    pub fn transition(&self, duration: f32) -> OrbitTransition {
+        OrbitTransition::to_pose(*self, duration)
+    }
+
+    /// A compact, versioned text form of the pose that is safe to put in a
+    /// URL query string, such as `v1,0,1.5707964,5,0,0,0`.
+    pub fn to_pose_string(&self) -> String {
+        format!(
+            "v{},{},{},{},{},{},{}",
+            POSE_STRING_VERSION,
+            self.x,
+            self.y,
+            self.distance,
+            self.center.x,
+            self.center.y,
+            self.center.z
+        )
+    }
+
+    /// Parses a pose written by [`OrbitPose::to_pose_string`], including by
+    /// older versions of the crate.
+    pub fn from_pose_string(string: &str) -> Result<OrbitPose, PoseError> {
+        let mut parts = string.trim().split(',');
+        let version = parts
+            .next()
+            .and_then(|version| version.strip_prefix('v'))
+            .ok_or(PoseError::Malformed)?;
+        let version = version.parse::<u32>().map_err(|_| PoseError::Malformed)?;
+        let values = parts
+            .map(|part| part.parse::<f32>().map_err(|_| PoseError::Malformed))
+            .collect::<Result<Vec<_>, _>>()?;
+        let values = pose_string_migrations()
+            .migrate(version, values)
+            .map_err(|_| PoseError::UnsupportedVersion)?;
+        let pose = match values[..] {
+            [x, y, distance, cx, cy, cz] => OrbitPose {
+                x,
+                y,
+                distance,
+                center: Vec3::new(cx, cy, cz),
+            },
+            _ => return Err(PoseError::Malformed),
+        };
+        if !(pose.x.is_finite()
+            && pose.y.is_finite()
+            && pose.distance.is_finite()
+            && pose.center.is_finite())
+        {
+            return Err(PoseError::NotFinite);
+        }
+        if pose.distance <= 0.0 {
+            return Err(PoseError::NonPositiveDistance);
+        }
+        Ok(pose)
+    }
+}
+
+impl From<&OrbitCamera> for OrbitPose {
+    fn from(camera: &OrbitCamera) -> Self {
+        OrbitPose {
+            x: camera.x,
+            y: camera.y,
+            distance: camera.distance,
+            center: camera.center,
+        }
+    }
+}
+
+/// Why a pose entered with [`OrbitCamera::set_exact`] or parsed with
+/// [`OrbitPose::from_pose_string`] was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PoseError {
+    NotFinite,
+    NonPositiveDistance,
+    PitchOutOfRange,
+    YawOutOfRange,
+    Malformed,
+    /// The pose string was written by a newer version of the format.
+    UnsupportedVersion,
+}
+
+impl fmt::Display for PoseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PoseError::NotFinite => "pose values must be finite",
+            PoseError::NonPositiveDistance => "distance must be greater than zero",
+            PoseError::PitchOutOfRange => "pitch is outside the camera's pitch range",
+            PoseError::YawOutOfRange => "yaw is outside the camera's yaw range",
+            PoseError::Malformed => "malformed pose string",
+            PoseError::UnsupportedVersion => "unsupported pose string version",
+        })
+    }
+}
+
+impl std::error::Error for PoseError {}
+
+impl OrbitCamera {
+    /// The target yaw, `x`, in degrees.
+    pub fn yaw_degrees(&self) -> f32 {
+        self.angles(&DEGREES).0
+    }
+
+    /// The target pitch, `y`, in degrees.
+    pub fn pitch_degrees(&self) -> f32 {
+        self.angles(&DEGREES).1
+    }
+
+    /// Sets the target pose from numeric input, with the angles in degrees.
+    /// Without an `animation` duration the target changes right away;
+    /// otherwise the camera is left alone and the returned transition should
+    /// be inserted on its entity.
+    pub fn set_exact(
+        &mut self,
+        yaw_degrees: f32,
+        pitch_degrees: f32,
+        distance: f32,
+        center: Vec3,
+        animation: Option<f32>,
+    ) -> Result<Option<OrbitTransition>, PoseError> {
+        if !yaw_degrees.is_finite()
+            || !pitch_degrees.is_finite()
+            || !distance.is_finite()
+            || !center.is_finite()
+        {
+            return Err(PoseError::NotFinite);
+        }
+        if distance <= 0.0 {
+            return Err(PoseError::NonPositiveDistance);
+        }
+        let (x, y) = DEGREES.from_external(yaw_degrees, pitch_degrees);
+        if !self.pitch_range.contains(&y) {
+            return Err(PoseError::PitchOutOfRange);
+        }
+        if !self.yaw_range.contains(&x) {
+            return Err(PoseError::YawOutOfRange);
+        }
+        let pose = OrbitPose {
+            x,
+            y,
+            distance,
+            center,
+        };
+        Ok(match animation {
+            Some(duration) => Some(pose.transition(duration)),
+            None => {
+                pose.apply(self);
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pose() -> OrbitPose {
+        OrbitPose {
+            x: -0.7,
+            y: 1.25,
+            distance: 12.5,
+            center: Vec3::new(1.0, -2.5, 0.125),
+        }
+    }
+
+    #[test]
+    fn round_trips_pose_strings() {
+        let string = pose().to_pose_string();
+        assert!(string.starts_with("v1,"));
+        assert_eq!(OrbitPose::from_pose_string(&string), Ok(pose()));
+        assert_eq!(
+            OrbitPose::from_pose_string(&format!("  {}\n", string)),
+            Ok(pose())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_pose_strings() {
+        let parse = OrbitPose::from_pose_string;
+        assert_eq!(parse(""), Err(PoseError::Malformed));
+        assert_eq!(parse("1,0,1,5,0,0,0"), Err(PoseError::Malformed));
+        assert_eq!(parse("v1,0,1,5,0,0"), Err(PoseError::Malformed));
+        assert_eq!(parse("v1,0,1,5,0,0,x"), Err(PoseError::Malformed));
+        assert_eq!(parse("v2,0,1,5,0,0,0"), Err(PoseError::UnsupportedVersion));
+        assert_eq!(parse("v1,NaN,1,5,0,0,0"), Err(PoseError::NotFinite));
+        assert_eq!(parse("v1,0,1,0,0,0,0"), Err(PoseError::NonPositiveDistance));
+    }
+}
@@ -0,0 +1,145 @@
+//! Snapping to views along the axes.
+
+use crate::split_screen::receives_input;
+use crate::trackball::wrap_angle;
+use crate::{ActiveOrbitCamera, InputCapture, OrbitCamera, OrbitCameraPlugin, OrbitTransition};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+/// A view along one of the axes, or from a corner, about `up`. Front looks
+/// from +Z, right from +X and top from above.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ViewPreset {
+    Front,
+    Back,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    /// From the front, right and above at once, with all three axes at the
+    /// same angle to the view.
+    Isometric,
+}
+
+impl ViewPreset {
+    /// The `x` and `y` of the view.
+    pub fn angles(&self) -> (f32, f32) {
+        match self {
+            ViewPreset::Front => (PI, FRAC_PI_2),
+            ViewPreset::Back => (0.0, FRAC_PI_2),
+            // Seen with the back of the scene at the top of the screen.
+            ViewPreset::Top => (PI, 0.0),
+            ViewPreset::Bottom => (PI, PI),
+            ViewPreset::Left => (FRAC_PI_2, FRAC_PI_2),
+            ViewPreset::Right => (-FRAC_PI_2, FRAC_PI_2),
+            ViewPreset::Isometric => (-3.0 * FRAC_PI_4, (1.0 / 3f32.sqrt()).acos()),
+        }
+    }
+
+    /// The view from the other side of the center.
+    pub fn opposite(&self) -> ViewPreset {
+        match self {
+            ViewPreset::Front => ViewPreset::Back,
+            ViewPreset::Back => ViewPreset::Front,
+            ViewPreset::Top => ViewPreset::Bottom,
+            ViewPreset::Bottom => ViewPreset::Top,
+            ViewPreset::Left => ViewPreset::Right,
+            ViewPreset::Right => ViewPreset::Left,
+            ViewPreset::Isometric => ViewPreset::Isometric,
+        }
+    }
+}
+
+impl OrbitCamera {
+    /// Turns the camera to `preset` at once, keeping its distance and center.
+    /// `x` turns the short way round, and `y` is kept within `pitch_range`.
+    pub fn view_preset(&mut self, preset: ViewPreset) {
+        let (x, y) = self.preset_angles(preset);
+        self.x = x;
+        self.y = y;
+    }
+
+    /// A transition turning the camera to `preset` over `duration` seconds.
+    pub fn view_preset_transition(&self, preset: ViewPreset, duration: f32) -> OrbitTransition {
+        let (x, y) = self.preset_angles(preset);
+        let mut transition = OrbitTransition::new(duration);
+        transition.x = Some(x);
+        transition.y = Some(y);
+        transition
+    }
+
+    fn preset_angles(&self, preset: ViewPreset) -> (f32, f32) {
+        let (x, y) = preset.angles();
+        let x = self.x + wrap_angle(x - self.x);
+        let y = y
+            .max(*self.pitch_range.start())
+            .min(*self.pitch_range.end());
+        (x, y)
+    }
+}
+
+/// Keys snapping the camera on the same entity to [`ViewPreset`]s. By default
+/// they're Blender's: 1, 3 and 7 on the numpad for the front, right and top
+/// views, with Ctrl for the opposite ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewPresetKeys {
+    pub keys: Vec<(KeyCode, ViewPreset)>,
+    /// Held to snap to the opposite view instead.
+    pub opposite_modifiers: Vec<KeyCode>,
+    /// Seconds the turn takes; zero snaps.
+    pub duration: f32,
+}
+
+impl Default for ViewPresetKeys {
+    fn default() -> Self {
+        ViewPresetKeys {
+            keys: vec![
+                (KeyCode::Numpad1, ViewPreset::Front),
+                (KeyCode::Numpad3, ViewPreset::Right),
+                (KeyCode::Numpad7, ViewPreset::Top),
+            ],
+            opposite_modifiers: vec![KeyCode::LControl, KeyCode::RControl],
+            duration: 0.3,
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn view_preset_system(
+        mut commands: Commands,
+        keyboard_input: Res<Input<KeyCode>>,
+        capture: Res<InputCapture>,
+        windows: Res<Windows>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        query: Query<(Entity, &OrbitCamera, &ViewPresetKeys, Option<&Camera>)>,
+    ) {
+        if capture.keyboard {
+            return;
+        }
+        for (entity, camera, keys, render_camera) in query.iter() {
+            let routed = receives_input(active.as_deref(), entity)
+                && camera.input_area.has_keyboard(&windows, render_camera);
+            if !camera.enabled || camera.rotation_locked || !routed {
+                continue;
+            }
+            let preset = keys
+                .keys
+                .iter()
+                .find(|(key, _)| keyboard_input.just_pressed(*key))
+                .map(|(_, preset)| *preset);
+            let preset = match preset {
+                Some(preset) => preset,
+                None => continue,
+            };
+            let opposite = keys
+                .opposite_modifiers
+                .iter()
+                .any(|key| keyboard_input.pressed(*key));
+            let preset = if opposite { preset.opposite() } else { preset };
+            commands
+                .entity(entity)
+                .insert(camera.view_preset_transition(preset, keys.duration));
+        }
+    }
+}
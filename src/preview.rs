@@ -0,0 +1,53 @@
+//! Previewing camera settings without changing the camera.
+
+use crate::{OrbitCamera, OrbitCameraState, OrbitPose};
+use bevy::prelude::*;
+
+/// How [`OrbitCamera::would_clamp`] would adjust a proposed pose.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClampReport {
+    /// The pose the camera would end up with.
+    pub pose: OrbitPose,
+    pub yaw_clamped: bool,
+    pub pitch_clamped: bool,
+    pub distance_clamped: bool,
+}
+
+impl ClampReport {
+    pub fn is_clamped(&self) -> bool {
+        self.yaw_clamped || self.pitch_clamped || self.distance_clamped
+    }
+}
+
+impl OrbitCamera {
+    /// The transform the camera is heading towards, once smoothing catches
+    /// up.
+    pub fn compute_transform(&self) -> Transform {
+        OrbitCameraState::from(self).transform(self.mode)
+    }
+
+    /// Where the camera's own limits would bite if it were moved to
+    /// `proposed`. Limits from other components, such as a
+    /// [`DistancePitchLimit`](crate::DistancePitchLimit), aren't included.
+    pub fn would_clamp(&self, proposed: &OrbitPose) -> ClampReport {
+        let mut pose = *proposed;
+        pose.x = proposed
+            .x
+            .max(*self.yaw_range.start())
+            .min(*self.yaw_range.end());
+        pose.y = proposed
+            .y
+            .max(*self.pitch_range.start())
+            .min(*self.pitch_range.end());
+        pose.distance = proposed
+            .distance
+            .max(*self.distance_range.start())
+            .min(*self.distance_range.end());
+        ClampReport {
+            pose,
+            yaw_clamped: pose.x != proposed.x,
+            pitch_clamped: pose.y != proposed.y,
+            distance_clamped: pose.distance != proposed.distance,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! A resource for apps with a single orbit camera.
+
+use crate::{OrbitCamera, OrbitCameraPlugin, OrbitCameraState, OrbitPose};
+use bevy::prelude::*;
+
+/// Insert this resource to read and drive one camera without querying for
+/// it. Each frame it mirrors the target and smoothed pose of `entity`, which
+/// is picked automatically while there is exactly one [`OrbitCamera`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PrimaryOrbitCamera {
+    pub entity: Option<Entity>,
+    target: Option<OrbitPose>,
+    state: Option<OrbitCameraState>,
+    pending: Option<OrbitPose>,
+}
+
+impl PrimaryOrbitCamera {
+    pub fn new(entity: Entity) -> Self {
+        PrimaryOrbitCamera {
+            entity: Some(entity),
+            ..Default::default()
+        }
+    }
+
+    /// The target pose as of the end of the last frame.
+    pub fn target(&self) -> Option<OrbitPose> {
+        self.pending.or(self.target)
+    }
+
+    /// The smoothed pose as of the end of the last frame.
+    pub fn state(&self) -> Option<&OrbitCameraState> {
+        self.state.as_ref()
+    }
+
+    /// Moves the camera's target to `pose` on the next update.
+    pub fn set_target(&mut self, pose: OrbitPose) {
+        self.pending = Some(pose);
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn primary_camera_apply_system(
+        primary: Option<ResMut<PrimaryOrbitCamera>>,
+        mut query: Query<(Entity, &mut OrbitCamera)>,
+    ) {
+        let mut primary = match primary {
+            Some(primary) => primary,
+            None => return,
+        };
+        if primary.entity.is_none() {
+            let mut cameras = query.iter_mut();
+            if let (Some((entity, _)), None) = (cameras.next(), cameras.next()) {
+                primary.entity = Some(entity);
+            }
+        }
+        let entity = match primary.entity {
+            Some(entity) => entity,
+            None => return,
+        };
+        if let Some(pose) = primary.pending.take() {
+            if let Ok((_, mut camera)) = query.get_mut(entity) {
+                pose.apply(&mut camera);
+            }
+        }
+    }
+
+    pub fn primary_camera_mirror_system(
+        primary: Option<ResMut<PrimaryOrbitCamera>>,
+        query: Query<(&OrbitCamera, Option<&OrbitCameraState>)>,
+    ) {
+        let mut primary = match primary {
+            Some(primary) => primary,
+            None => return,
+        };
+        let camera = primary.entity.and_then(|entity| query.get(entity).ok());
+        primary.target = camera.map(|(camera, _)| OrbitPose::from(camera));
+        primary.state = camera.and_then(|(_, state)| state.copied());
+    }
+}
@@ -0,0 +1,126 @@
+//! Switching between perspective and orthographic projections.
+
+use crate::{CameraSuspended, OrbitCameraPlugin, OrbitCameraState};
+use bevy::prelude::*;
+use bevy::render::camera::{Camera, CameraProjection, PerspectiveProjection};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProjectionKind {
+    Perspective,
+    Orthographic,
+}
+
+/// Animates a perspective camera to and from an orthographic projection that
+/// frames the orbit center the same way at the current distance, as CAD
+/// packages do. The camera keeps its [`PerspectiveProjection`], whose field of
+/// view and clip planes define both projections.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectionSwitch {
+    pub target: ProjectionKind,
+    /// How long, in seconds, a switch takes.
+    pub duration: f32,
+    blend: f32,
+}
+
+impl ProjectionSwitch {
+    pub fn new(kind: ProjectionKind) -> Self {
+        ProjectionSwitch {
+            target: kind,
+            duration: 0.3,
+            blend: Self::goal(kind),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.target = match self.target {
+            ProjectionKind::Perspective => ProjectionKind::Orthographic,
+            ProjectionKind::Orthographic => ProjectionKind::Perspective,
+        };
+    }
+
+    /// How far the projection is from perspective, from zero to one.
+    pub fn blend(&self) -> f32 {
+        self.blend
+    }
+
+    pub fn is_switching(&self) -> bool {
+        self.blend != Self::goal(self.target)
+    }
+
+    /// The blended projection, or `None` when fully perspective.
+    pub fn matrix(&self, projection: &PerspectiveProjection, distance: f32) -> Option<Mat4> {
+        if self.blend <= 0.0 {
+            return None;
+        }
+        let t = self.blend;
+        let eased = t * t * (3.0 - 2.0 * t);
+        let perspective = projection.get_projection_matrix();
+        let orthographic = matched_orthographic(projection, distance);
+        Some(perspective * (1.0 - eased) + orthographic * eased)
+    }
+
+    fn goal(kind: ProjectionKind) -> f32 {
+        match kind {
+            ProjectionKind::Perspective => 0.0,
+            ProjectionKind::Orthographic => 1.0,
+        }
+    }
+}
+
+impl Default for ProjectionSwitch {
+    fn default() -> Self {
+        ProjectionSwitch::new(ProjectionKind::Perspective)
+    }
+}
+
+/// The orthographic projection showing as much at `distance` as `projection`.
+pub fn matched_orthographic(projection: &PerspectiveProjection, distance: f32) -> Mat4 {
+    let half_height = distance * (projection.fov / 2.0).tan();
+    let half_width = half_height * projection.aspect_ratio;
+    Mat4::orthographic_rh(
+        -half_width,
+        half_width,
+        -half_height,
+        half_height,
+        projection.near,
+        projection.far,
+    )
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn projection_switch_system(
+        time: Res<Time>,
+        mut query: Query<
+            (
+                &OrbitCameraState,
+                &PerspectiveProjection,
+                &mut Camera,
+                &mut ProjectionSwitch,
+            ),
+            Without<CameraSuspended>,
+        >,
+    ) {
+        for (state, projection, mut camera, mut switch) in query.iter_mut() {
+            let switching = switch.is_switching();
+            if switching {
+                let goal = ProjectionSwitch::goal(switch.target);
+                let step = if switch.duration > 0.0 {
+                    time.delta_seconds() / switch.duration
+                } else {
+                    1.0
+                };
+                switch.blend = if goal > switch.blend {
+                    (switch.blend + step).min(goal)
+                } else {
+                    (switch.blend - step).max(goal)
+                };
+            }
+            match switch.matrix(projection, state.distance) {
+                Some(matrix) => camera.projection_matrix = matrix,
+                None if switching => camera.projection_matrix = projection.get_projection_matrix(),
+                None => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Keeping the eye on a track.
+
+use crate::camera_command::set_direction;
+use crate::{CameraMode, CameraSuspended, OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+
+/// Where along a [`CameraRail`] the eye is placed, from zero at the first
+/// point to one at the last.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RailPosition {
+    /// The point of the rail closest to the orbit center.
+    Closest,
+    /// Set by game code, such as from the progress through a level.
+    At(f32),
+    /// How far the orbit center has got from `start` towards `end`.
+    Progress { start: Vec3, end: Vec3 },
+}
+
+/// Moves the eye of an orbiting camera along a Catmull-Rom spline through
+/// `points`, while it keeps looking at the orbit center, for scripted
+/// sections. The angles and distance are set from the eye every frame, so
+/// smoothing and collision still apply, but the user can't rotate or zoom.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraRail {
+    pub points: Vec<Vec3>,
+    pub position: RailPosition,
+}
+
+/// Samples per span when looking for the closest point.
+const CLOSEST_SAMPLES: usize = 16;
+
+impl CameraRail {
+    pub fn new(points: Vec<Vec3>, position: RailPosition) -> Self {
+        CameraRail { points, position }
+    }
+
+    /// Moves the rail along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        for point in self.points.iter_mut() {
+            *point += offset;
+        }
+        if let RailPosition::Progress { start, end } = &mut self.position {
+            *start += offset;
+            *end += offset;
+        }
+    }
+
+    /// The point at `t`, from zero at the first point to one at the last.
+    pub fn evaluate(&self, t: f32) -> Option<Vec3> {
+        let last = self.points.len().checked_sub(1)?;
+        if last == 0 {
+            return self.points.first().copied();
+        }
+        let along = t.clamp(0.0, 1.0) * last as f32;
+        let span = (along.floor() as usize).min(last - 1);
+        let t = along - span as f32;
+        let point = |index: isize| self.points[index.clamp(0, last as isize) as usize];
+        let i = span as isize;
+        let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+        let t2 = t * t;
+        let t3 = t2 * t;
+        Some(
+            0.5 * (2.0 * p1
+                + (p2 - p0) * t
+                + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3),
+        )
+    }
+
+    /// The `t` of the point closest to `target`.
+    pub fn closest(&self, target: Vec3) -> f32 {
+        let samples = self.points.len().saturating_sub(1) * CLOSEST_SAMPLES;
+        if samples == 0 {
+            return 0.0;
+        }
+        (0..=samples)
+            .map(|sample| sample as f32 / samples as f32)
+            .filter_map(|t| Some((t, self.evaluate(t)?.distance_squared(target))))
+            .fold((0.0, f32::INFINITY), |best, candidate| {
+                if candidate.1 < best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .0
+    }
+
+    /// The `t` the eye is at for the orbit center `center`.
+    pub fn parameter(&self, center: Vec3) -> f32 {
+        match self.position {
+            RailPosition::Closest => self.closest(center),
+            RailPosition::At(t) => t,
+            RailPosition::Progress { start, end } => {
+                let line = end - start;
+                let length = line.length_squared();
+                if length > 0.0 {
+                    (center - start).dot(line) / length
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn camera_rail_system(
+        mut query: Query<(&mut OrbitCamera, &CameraRail), Without<CameraSuspended>>,
+    ) {
+        for (mut camera, rail) in query.iter_mut() {
+            if camera.mode != CameraMode::Orbit || !camera.enabled {
+                continue;
+            }
+            let eye = match rail.evaluate(rail.parameter(camera.center)) {
+                Some(eye) => eye,
+                None => continue,
+            };
+            let back = eye - camera.center;
+            let distance = back.length();
+            if distance < f32::EPSILON {
+                continue;
+            }
+            camera.distance = distance;
+            set_direction(&mut camera, back / distance);
+        }
+    }
+}
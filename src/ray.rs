@@ -0,0 +1,181 @@
+//! Picking points in the world from the screen.
+//!
+//! Features that need to know what's under the cursor, or what's in the
+//! camera's way, go through the [`Raycaster`] resource, so apps can plug in
+//! their own picking and physics. With the `bevy_mod_raycast` feature,
+//! [`ModRaycast`] picks meshes marked with `RayCastMesh<OrbitRaycastSet>`.
+
+use crate::pivot::up_rotation;
+use crate::OrbitCamera;
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// A half-line in world space, usually cast from the camera through a point
+/// on the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Casts a ray through `screen_position`, in logical pixels from the
+    /// bottom-left corner of a viewport of `screen_size`, the same space as
+    /// `Window::cursor_position` and touch positions.
+    pub fn from_screen(
+        screen_position: Vec2,
+        screen_size: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Ray> {
+        if screen_size.x <= 0.0 || screen_size.y <= 0.0 {
+            return None;
+        }
+        let ndc = screen_position / screen_size * 2.0 - Vec2::ONE;
+        let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix.inverse();
+        let near = ndc_to_world.project_point3(ndc.extend(0.0));
+        let far = ndc_to_world.project_point3(ndc.extend(1.0));
+        let direction = (far - near).normalize();
+        if !direction.is_finite() {
+            return None;
+        }
+        Some(Ray {
+            origin: near,
+            direction,
+        })
+    }
+
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// The point where the ray crosses the plane through `point` with `normal`.
+    pub fn intersect_plane(&self, point: Vec3, normal: Vec3) -> Option<Vec3> {
+        let denominator = self.direction.dot(normal);
+        if denominator.abs() < f32::EPSILON {
+            return None;
+        }
+        let t = (point - self.origin).dot(normal) / denominator;
+        if t < 0.0 {
+            return None;
+        }
+        Some(self.at(t))
+    }
+}
+
+/// Everything a [`RaycastProvider`] may use to pick a point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RaycastQuery {
+    pub camera: Entity,
+    pub screen_position: Vec2,
+    pub ray: Ray,
+    pub center: Vec3,
+    /// The camera's up direction, normalized.
+    pub up: Vec3,
+}
+
+/// Where a [`RaycastProvider::cast`] met a surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    pub point: Vec3,
+    /// The surface normal, facing back along the ray.
+    pub normal: Vec3,
+    /// How far along the ray the hit is.
+    pub distance: f32,
+}
+
+/// Resolves points on the screen to points in the world, for features such
+/// as focusing on what's under the cursor, and finds the solid geometry that
+/// a [`CameraCollision`](crate::CameraCollision) keeps the camera out of.
+pub trait RaycastProvider: Send + Sync + 'static {
+    fn raycast(&self, query: &RaycastQuery) -> Option<Vec3>;
+
+    /// The nearest solid surface along `ray` within `max_distance`, usually
+    /// found through a physics engine. Providers that only pick points, like
+    /// the default, hit nothing, so cameras pass through everything.
+    fn cast(&self, _ray: &Ray, _max_distance: f32) -> Option<RayHit> {
+        None
+    }
+}
+
+/// The [`RaycastProvider`] used by the plugin. Defaults to a [`GroundPlane`].
+pub struct Raycaster(pub Box<dyn RaycastProvider>);
+
+impl Raycaster {
+    pub fn new(provider: impl RaycastProvider) -> Self {
+        Raycaster(Box::new(provider))
+    }
+
+    pub fn raycast(&self, query: &RaycastQuery) -> Option<Vec3> {
+        self.0.raycast(query)
+    }
+
+    pub fn cast(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        self.0.cast(ray, max_distance)
+    }
+}
+
+impl Default for Raycaster {
+    fn default() -> Self {
+        Raycaster::new(GroundPlane::default())
+    }
+}
+
+/// Hits an infinite plane. Without a `point`, the plane passes through the
+/// current orbit center; without a `normal`, it faces the camera's up, or +Y
+/// when blocking cameras. Only a plane fixed at a `point` blocks cameras.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GroundPlane {
+    pub point: Option<Vec3>,
+    pub normal: Option<Vec3>,
+}
+
+impl RaycastProvider for GroundPlane {
+    fn raycast(&self, query: &RaycastQuery) -> Option<Vec3> {
+        query.ray.intersect_plane(
+            self.point.unwrap_or(query.center),
+            self.normal.unwrap_or(query.up),
+        )
+    }
+
+    fn cast(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        let normal = self.normal.unwrap_or(Vec3::Y).normalize();
+        let point = ray.intersect_plane(self.point?, normal)?;
+        let distance = point.distance(ray.origin);
+        if distance > max_distance || !normal.is_finite() {
+            return None;
+        }
+        Some(RayHit {
+            point,
+            normal: if ray.direction.dot(normal) > 0.0 {
+                -normal
+            } else {
+                normal
+            },
+            distance,
+        })
+    }
+}
+
+/// Casts through `screen_position` of the camera's window with the
+/// configured [`Raycaster`].
+pub(crate) fn pick(
+    raycaster: &Raycaster,
+    entity: Entity,
+    orbit: &OrbitCamera,
+    camera: &Camera,
+    transform: &GlobalTransform,
+    windows: &Windows,
+    screen_position: Vec2,
+) -> Option<Vec3> {
+    let window = windows.get(camera.window)?;
+    let size = Vec2::new(window.width(), window.height());
+    let ray = Ray::from_screen(screen_position, size, camera, transform)?;
+    raycaster.raycast(&RaycastQuery {
+        camera: entity,
+        screen_position,
+        ray,
+        center: orbit.center,
+        up: up_rotation(orbit.up) * Vec3::Y,
+    })
+}
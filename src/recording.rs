@@ -0,0 +1,333 @@
+//! Recording control events to reproduce camera behaviour later.
+
+use crate::{CameraEvents, OrbitCameraPlugin, RoutedCameraEvents};
+use bevy::prelude::*;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+const RECORDING_HEADER: &str = "orbit-input v2";
+/// Recordings from before routed events, which read the same otherwise.
+const RECORDING_HEADER_V1: &str = "orbit-input v1";
+
+/// The [`CameraEvents`] and [`RoutedCameraEvents`] of one frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecordedFrame {
+    /// Seconds since the recording started.
+    pub time: f32,
+    pub events: Vec<CameraEvents>,
+    /// Events for one camera, which name it by its entity, so they only replay
+    /// onto the same camera within the same run of the app.
+    pub routed: Vec<RoutedCameraEvents>,
+}
+
+fn write_event(f: &mut fmt::Formatter, event: &CameraEvents) -> fmt::Result {
+    match event {
+        CameraEvents::Orbit(delta) => writeln!(f, "orbit {} {}", delta.x, delta.y),
+        CameraEvents::Pan(delta) => writeln!(f, "pan {} {}", delta.x, delta.y),
+        CameraEvents::Zoom(amount) => writeln!(f, "zoom {}", amount),
+    }
+}
+
+fn parse_event(kind: &str, values: &[f32]) -> Option<CameraEvents> {
+    match (kind, values) {
+        ("orbit", [x, y]) => Some(CameraEvents::Orbit(Vec2::new(*x, *y))),
+        ("pan", [x, y]) => Some(CameraEvents::Pan(Vec2::new(*x, *y))),
+        ("zoom", [amount]) => Some(CameraEvents::Zoom(*amount)),
+        _ => None,
+    }
+}
+
+/// Control events with their timestamps, as captured by an [`InputRecorder`].
+/// The text form, written by `to_string` and read by `parse`, can be attached
+/// to bug reports.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputRecording {
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<InputRecording> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// The time of the last frame.
+    pub fn duration(&self) -> f32 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+    }
+}
+
+impl fmt::Display for InputRecording {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", RECORDING_HEADER)?;
+        for frame in &self.frames {
+            writeln!(f, "frame {}", frame.time)?;
+            for event in &frame.events {
+                write_event(f, event)?;
+            }
+            for routed in &frame.routed {
+                write!(f, "to {} ", routed.camera.to_bits())?;
+                write_event(f, &routed.event)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why a recording couldn't be read, with the line it failed on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordingParseError {
+    pub line: usize,
+}
+
+impl fmt::Display for RecordingParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid input recording at line {}", self.line)
+    }
+}
+
+impl std::error::Error for RecordingParseError {}
+
+impl FromStr for InputRecording {
+    type Err = RecordingParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, header))
+                if header.trim() == RECORDING_HEADER || header.trim() == RECORDING_HEADER_V1 => {}
+            _ => return Err(RecordingParseError { line: 1 }),
+        }
+        let mut recording = InputRecording::default();
+        for (index, line) in lines {
+            let error = RecordingParseError { line: index + 1 };
+            let mut words = line.split_whitespace();
+            let mut kind = match words.next() {
+                Some(kind) => kind,
+                None => continue,
+            };
+            let mut camera = None;
+            if kind == "to" {
+                let bits = words.next().and_then(|bits| bits.parse::<u64>().ok());
+                camera = Some(Entity::from_bits(bits.ok_or_else(|| error.clone())?));
+                kind = words.next().ok_or_else(|| error.clone())?;
+            }
+            let values = words
+                .map(|word| word.parse::<f32>().map_err(|_| error.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let ("frame", [time], None) = (kind, &values[..], camera) {
+                recording.frames.push(RecordedFrame {
+                    time: *time,
+                    ..Default::default()
+                });
+                continue;
+            }
+            let event = parse_event(kind, &values).ok_or_else(|| error.clone())?;
+            let frame = recording.frames.last_mut().ok_or(error)?;
+            match camera {
+                Some(camera) => frame.routed.push(RoutedCameraEvents { camera, event }),
+                None => frame.events.push(event),
+            }
+        }
+        Ok(recording)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum RecorderMode {
+    Idle,
+    Recording {
+        started: Option<f64>,
+        recording: InputRecording,
+    },
+    Replaying {
+        started: Option<f64>,
+        recording: InputRecording,
+        next: usize,
+    },
+}
+
+/// Records the [`CameraEvents`] and [`RoutedCameraEvents`] sent each frame, or replays a recording in
+/// place of live input. Smoothing still follows the real frame times, so
+/// replays match most closely at a steady frame rate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputRecorder {
+    mode: RecorderMode,
+}
+
+impl Default for InputRecorder {
+    fn default() -> Self {
+        InputRecorder {
+            mode: RecorderMode::Idle,
+        }
+    }
+}
+
+impl InputRecorder {
+    pub fn start_recording(&mut self) {
+        self.mode = RecorderMode::Recording {
+            started: None,
+            recording: InputRecording::default(),
+        };
+    }
+
+    /// Stops recording or replaying, returning what was recorded.
+    pub fn stop(&mut self) -> Option<InputRecording> {
+        match std::mem::replace(&mut self.mode, RecorderMode::Idle) {
+            RecorderMode::Recording { recording, .. } => Some(recording),
+            _ => None,
+        }
+    }
+
+    /// Replays `recording`, ignoring live input until it ends.
+    pub fn replay(&mut self, recording: InputRecording) {
+        self.mode = RecorderMode::Replaying {
+            started: None,
+            recording,
+            next: 0,
+        };
+    }
+
+    pub fn is_recording(&self) -> bool {
+        matches!(self.mode, RecorderMode::Recording { .. })
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.mode, RecorderMode::Replaying { .. })
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn record_input_system(
+        time: Res<Time>,
+        mut recorder: ResMut<InputRecorder>,
+        mut events: EventReader<CameraEvents>,
+        mut routed: EventReader<RoutedCameraEvents>,
+    ) {
+        if !recorder.is_recording() {
+            return;
+        }
+        if let RecorderMode::Recording { started, recording } = &mut recorder.mode {
+            let now = time.seconds_since_startup();
+            let started = *started.get_or_insert(now);
+            let events: Vec<_> = events.iter().copied().collect();
+            let routed: Vec<_> = routed.iter().copied().collect();
+            if !events.is_empty() || !routed.is_empty() {
+                recording.frames.push(RecordedFrame {
+                    time: (now - started) as f32,
+                    events,
+                    routed,
+                });
+            }
+        }
+    }
+
+    pub fn replay_input_system(
+        time: Res<Time>,
+        mut recorder: ResMut<InputRecorder>,
+        mut events: EventWriter<CameraEvents>,
+        mut routed: EventWriter<RoutedCameraEvents>,
+    ) {
+        if !recorder.is_replaying() {
+            return;
+        }
+        let mut finished = false;
+        if let RecorderMode::Replaying {
+            started,
+            recording,
+            next,
+        } = &mut recorder.mode
+        {
+            let now = time.seconds_since_startup();
+            let elapsed = (now - *started.get_or_insert(now)) as f32;
+            while let Some(frame) = recording.frames.get(*next) {
+                if frame.time > elapsed {
+                    break;
+                }
+                for event in &frame.events {
+                    events.send(*event);
+                }
+                for event in &frame.routed {
+                    routed.send(*event);
+                }
+                *next += 1;
+            }
+            finished = *next >= recording.frames.len();
+        }
+        if finished {
+            recorder.mode = RecorderMode::Idle;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> InputRecording {
+        InputRecording {
+            frames: vec![
+                RecordedFrame {
+                    time: 0.0,
+                    events: vec![CameraEvents::Orbit(Vec2::new(1.5, -2.0))],
+                    routed: Vec::new(),
+                },
+                RecordedFrame {
+                    time: 0.016,
+                    events: vec![CameraEvents::Pan(Vec2::new(0.25, 4.0))],
+                    routed: vec![RoutedCameraEvents {
+                        camera: Entity::new(7),
+                        event: CameraEvents::Zoom(-1.0),
+                    }],
+                },
+                RecordedFrame {
+                    time: 0.5,
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_recordings() {
+        let text = recording().to_string();
+        assert!(text.starts_with("orbit-input v2\n"));
+        assert!(text.contains(&format!("to {} zoom -1\n", Entity::new(7).to_bits())));
+        assert_eq!(text.parse::<InputRecording>(), Ok(recording()));
+        assert_eq!(recording().duration(), 0.5);
+    }
+
+    #[test]
+    fn reads_v1_recordings() {
+        let text = "orbit-input v1\nframe 0.25\norbit 1 2\n\nzoom 3\n";
+        let recording: InputRecording = text.parse().unwrap();
+        assert_eq!(
+            recording.frames,
+            vec![RecordedFrame {
+                time: 0.25,
+                events: vec![
+                    CameraEvents::Orbit(Vec2::new(1.0, 2.0)),
+                    CameraEvents::Zoom(3.0)
+                ],
+                routed: Vec::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_the_failing_line() {
+        let line = |text: &str| text.parse::<InputRecording>().map_err(|error| error.line);
+        assert_eq!(line("orbit-input v3\n"), Err(1));
+        assert_eq!(line("orbit-input v2\norbit 1 2\n"), Err(2));
+        assert_eq!(line("orbit-input v2\nframe 0\norbit 1\n"), Err(3));
+        assert_eq!(line("orbit-input v2\nframe 0\nto x zoom 1\n"), Err(3));
+        assert_eq!(line("orbit-input v2\nframe 0\nto 7 frame 1\n"), Err(3));
+    }
+}
@@ -0,0 +1,94 @@
+//! Keeping the framing steady while the window is resized.
+
+use crate::{OrbitCamera, OrbitCameraPlugin, OrbitCameraState};
+use bevy::prelude::*;
+use bevy::render::camera::{
+    Camera, CameraProjection, OrthographicProjection, PerspectiveProjection,
+};
+use bevy::utils::HashMap;
+use bevy::window::{WindowId, WindowResized};
+
+/// What stays the same on screen when the window is resized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResizeAnchor {
+    /// The visible height of the scene, as in Bevy's perspective cameras.
+    Height,
+    /// The visible width of the scene.
+    Width,
+    /// The size of objects in pixels, so a larger window shows more.
+    PixelSize,
+}
+
+impl ResizeAnchor {
+    /// Scales the visible half-height for a window going from `old` to `new`.
+    pub fn rescale(&self, half_height: f32, old: Vec2, new: Vec2) -> f32 {
+        if old.x <= 0.0 || old.y <= 0.0 || new.x <= 0.0 || new.y <= 0.0 {
+            return half_height;
+        }
+        match self {
+            ResizeAnchor::Height => half_height,
+            ResizeAnchor::Width => half_height * (old.x / old.y) / (new.x / new.y),
+            ResizeAnchor::PixelSize => half_height * new.y / old.y,
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn resize_anchor_system(
+        mut sizes: Local<HashMap<WindowId, Vec2>>,
+        mut resized: EventReader<WindowResized>,
+        windows: Res<Windows>,
+        mut query: Query<(
+            &mut OrbitCamera,
+            &mut OrbitCameraState,
+            &Camera,
+            Option<&PerspectiveProjection>,
+            Option<&OrthographicProjection>,
+        )>,
+    ) {
+        let mut latest = HashMap::default();
+        for event in resized.iter() {
+            latest.insert(event.id, Vec2::new(event.width, event.height));
+        }
+        for (id, new) in latest {
+            let old = match sizes.get(&id) {
+                Some(old) => *old,
+                None => continue,
+            };
+            for (mut camera, mut state, render_camera, perspective, orthographic) in
+                query.iter_mut()
+            {
+                let anchor = match camera.resize_anchor {
+                    Some(anchor) if render_camera.window == id => anchor,
+                    _ => continue,
+                };
+                if let Some(perspective) = perspective {
+                    let fov = camera.fov.unwrap_or(perspective.fov);
+                    let tan = anchor.rescale((fov / 2.0).tan(), old, new);
+                    let fov = 2.0 * tan.atan();
+                    camera.fov = Some(fov);
+                    state.fov = Some(fov);
+                } else if let Some(orthographic) = orthographic {
+                    let scale = camera.scale.unwrap_or(orthographic.scale);
+                    let half_height = (orthographic.top - orthographic.bottom) / 2.0 * scale;
+                    let wanted = anchor.rescale(half_height, old, new);
+                    // What the projection will cover once Bevy updates it.
+                    let mut resized = orthographic.clone();
+                    resized.update(new.x, new.y);
+                    let unscaled = (resized.top - resized.bottom) / 2.0;
+                    if unscaled > 0.0 {
+                        camera.scale = Some(wanted / unscaled);
+                        state.scale = camera.scale;
+                    }
+                }
+            }
+        }
+
+        sizes.clear();
+        for window in windows.iter() {
+            sizes.insert(window.id(), Vec2::new(window.width(), window.height()));
+        }
+    }
+}
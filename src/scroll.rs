@@ -0,0 +1,105 @@
+//! Following the platform's scroll direction.
+
+/// Whether the platform has natural scrolling turned on, which moves content
+/// along with the fingers and so reverses the wheel. With the
+/// `natural_scrolling` feature, it's read from the system settings on macOS
+/// and from Safari in web builds; elsewhere apps can set it themselves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ScrollDirection {
+    pub natural: Option<bool>,
+}
+
+#[cfg(all(
+    feature = "natural_scrolling",
+    any(target_os = "macos", target_arch = "wasm32")
+))]
+mod detect {
+    use super::ScrollDirection;
+    use crate::OrbitCameraPlugin;
+    use bevy::prelude::*;
+    #[cfg(target_arch = "wasm32")]
+    use std::sync::{Arc, Mutex};
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen::{closure::Closure, prelude::*, JsCast};
+    #[cfg(target_arch = "wasm32")]
+    use web_sys::Event;
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen]
+    extern "C" {
+        type SafariWheelEvent;
+
+        #[wasm_bindgen(method, getter, js_name = webkitDirectionInvertedFromDevice)]
+        fn inverted_from_device(this: &SafariWheelEvent) -> Option<bool>;
+    }
+
+    /// The direction reported by the browser's `wheel` listener.
+    #[cfg(target_arch = "wasm32")]
+    #[derive(Clone, Default)]
+    pub struct BrowserScrollDirection(Arc<Mutex<Option<bool>>>);
+
+    impl OrbitCameraPlugin {
+        /// Reads the global setting, which is missing until it's first
+        /// changed and on by default.
+        #[cfg(target_os = "macos")]
+        pub fn macos_scroll_direction_system(mut direction: ResMut<ScrollDirection>) {
+            if direction.natural.is_some() {
+                return;
+            }
+            let output = match std::process::Command::new("defaults")
+                .args(&["read", "-g", "com.apple.swipescrolldirection"])
+                .output()
+            {
+                Ok(output) => output,
+                Err(_) => return,
+            };
+            direction.natural = if output.status.success() {
+                match String::from_utf8_lossy(&output.stdout).trim() {
+                    "0" => Some(false),
+                    "1" => Some(true),
+                    _ => None,
+                }
+            } else {
+                Some(true)
+            };
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        pub fn listen_scroll_direction_system(latest: Res<BrowserScrollDirection>) {
+            let window = match web_sys::window() {
+                Some(window) => window,
+                None => return,
+            };
+            let latest = latest.0.clone();
+            let listener = Closure::wrap(Box::new(move |event: Event| {
+                let inverted = event
+                    .unchecked_ref::<SafariWheelEvent>()
+                    .inverted_from_device();
+                if let (Some(inverted), Ok(mut latest)) = (inverted, latest.lock()) {
+                    *latest = Some(inverted);
+                }
+            }) as Box<dyn FnMut(Event)>);
+            if window
+                .add_event_listener_with_callback("wheel", listener.as_ref().unchecked_ref())
+                .is_ok()
+            {
+                // The listener lives as long as the page.
+                listener.forget();
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        pub fn browser_scroll_direction_system(
+            latest: Res<BrowserScrollDirection>,
+            mut direction: ResMut<ScrollDirection>,
+        ) {
+            if let Ok(latest) = latest.0.lock() {
+                if latest.is_some() && direction.natural != *latest {
+                    direction.natural = *latest;
+                }
+            }
+        }
+    }
+}
+#[cfg(all(feature = "natural_scrolling", target_arch = "wasm32"))]
+pub use detect::BrowserScrollDirection;
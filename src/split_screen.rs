@@ -0,0 +1,258 @@
+//! Giving each player of a split-screen game their own camera.
+
+use crate::{CameraEvents, InputArea, OrbitCamera, OrbitCameraPlugin};
+use bevy::core::FloatOrd;
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// [`CameraEvents`] for one camera only, such as from the gamepad its
+/// [`GamepadBindings`](crate::GamepadBindings) are bound to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoutedCameraEvents {
+    pub camera: Entity,
+    pub event: CameraEvents,
+}
+
+/// Insert this resource to send the built-in mouse, keyboard, touch and
+/// unbound gamepad input to one camera only, such as when split-screen views
+/// or a picture-in-picture overlap the same window. Other cameras are left
+/// alone until another is made active.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ActiveOrbitCamera {
+    pub entity: Option<Entity>,
+    /// Makes the camera under the cursor active whenever no mouse button is
+    /// held, so each view is driven while the cursor is over it and drags stay
+    /// with the view they started in. Where views overlap, the one with the
+    /// smallest [`InputArea`] wins.
+    pub follow_cursor: bool,
+}
+
+impl ActiveOrbitCamera {
+    pub fn new(entity: Entity) -> Self {
+        ActiveOrbitCamera {
+            entity: Some(entity),
+            follow_cursor: false,
+        }
+    }
+
+    /// Follows the cursor, starting with no camera active.
+    pub fn following_cursor() -> Self {
+        ActiveOrbitCamera {
+            entity: None,
+            follow_cursor: true,
+        }
+    }
+
+    pub fn set(&mut self, entity: Entity) {
+        self.entity = Some(entity);
+    }
+
+    pub fn is_active(&self, entity: Entity) -> bool {
+        self.entity == Some(entity)
+    }
+}
+
+/// Whether the built-in input reaches the camera `entity`.
+pub(crate) fn receives_input(active: Option<&ActiveOrbitCamera>, entity: Entity) -> bool {
+    active.map_or(true, |active| active.is_active(entity))
+}
+
+struct GatheredInput {
+    camera: Entity,
+    scoped: bool,
+    alone: bool,
+    events: Vec<CameraEvents>,
+}
+
+/// The built-in input of one system for one frame, gathered from every camera
+/// and sent once it's all in. When every camera that reads the
+/// [`CameraEvents`] sent to all of them would get the same events, those are
+/// sent once; otherwise each camera is sent its own as [`RoutedCameraEvents`],
+/// so no camera gets the input twice or another camera's input.
+#[derive(Default)]
+pub(crate) struct CameraInput {
+    cameras: Vec<GatheredInput>,
+}
+
+impl CameraInput {
+    /// Where to put the events of `camera`, which go to it alone if it has a
+    /// scoped `area` or `routed` is set. Every camera the system sees should be
+    /// added, even ones that get no events, so they're not sent another's.
+    pub(crate) fn camera(
+        &mut self,
+        camera: Entity,
+        area: InputArea,
+        routed: bool,
+    ) -> &mut Vec<CameraEvents> {
+        let index = self.cameras.len();
+        self.cameras.push(GatheredInput {
+            camera,
+            scoped: area.is_scoped(),
+            alone: area.is_scoped() || routed,
+            events: Vec::new(),
+        });
+        &mut self.cameras[index].events
+    }
+
+    pub(crate) fn send(
+        self,
+        events: &mut EventWriter<CameraEvents>,
+        routed: &mut EventWriter<RoutedCameraEvents>,
+    ) {
+        // Scoped cameras ignore the events sent to every camera.
+        let mut unscoped = self.cameras.iter().filter(|input| !input.scoped);
+        let shared = match unscoped.next() {
+            Some(first) if !first.alone => {
+                unscoped.all(|input| !input.alone && input.events == first.events)
+            }
+            _ => false,
+        };
+        let mut broadcast = shared;
+        for input in self.cameras {
+            if input.alone || !shared {
+                for event in input.events {
+                    routed.send(RoutedCameraEvents {
+                        camera: input.camera,
+                        event,
+                    });
+                }
+            } else if std::mem::take(&mut broadcast) {
+                events.send_batch(input.events.into_iter());
+            }
+        }
+    }
+}
+
+/// Lays out `count` views in a grid over a window `window_size` big, filling
+/// rows from the top left. Views in the last row share its width when it
+/// isn't full. Each view is given by its bottom-left and top-right corners,
+/// in the same space as `Window::cursor_position`.
+pub fn split_screen_grid(count: usize, window_size: Vec2) -> Vec<(Vec2, Vec2)> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let columns = (count as f32).sqrt().ceil() as usize;
+    let rows = (count + columns - 1) / columns;
+    let height = window_size.y / rows as f32;
+    (0..count)
+        .map(|index| {
+            let row = index / columns;
+            let in_row = (count - row * columns).min(columns);
+            let width = window_size.x / in_row as f32;
+            let column = index % columns;
+            let min = Vec2::new(width * column as f32, height * (rows - row - 1) as f32);
+            (min, min + Vec2::new(width, height))
+        })
+        .collect()
+}
+
+/// Spawns `count` perspective cameras with an [`OrbitCamera`] each, scoped
+/// to their view of a [`split_screen_grid`] over a window `window_size` big
+/// and bound to the gamepad at the same index of `gamepads`. Cameras past the
+/// end of `gamepads` move with every unbound gamepad. Returns the cameras in
+/// the order of their views, for the app to add its own components to.
+pub fn spawn_split_screen(
+    commands: &mut Commands,
+    count: usize,
+    window_size: Vec2,
+    gamepads: &[Gamepad],
+) -> Vec<Entity> {
+    split_screen_grid(count, window_size)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (min, max))| {
+            let mut camera = OrbitCamera {
+                input_area: InputArea::Rect { min, max },
+                ..Default::default()
+            };
+            camera.gamepad.gamepad = gamepads.get(index).copied();
+            commands
+                .spawn_bundle(PerspectiveCameraBundle::default())
+                .insert(camera)
+                .id()
+        })
+        .collect()
+}
+
+impl OrbitCameraPlugin {
+    pub fn active_camera_system(
+        active: Option<ResMut<ActiveOrbitCamera>>,
+        mouse_button_input: Res<Input<MouseButton>>,
+        windows: Res<Windows>,
+        query: Query<(Entity, &OrbitCamera, Option<&Camera>)>,
+    ) {
+        let mut active = match active {
+            Some(active) if active.follow_cursor => active,
+            _ => return,
+        };
+        let held = mouse_button_input.get_pressed().next().is_some();
+        let pressed = mouse_button_input.get_just_pressed().next().is_some();
+        if held && !pressed {
+            return;
+        }
+        let under = query
+            .iter()
+            .filter(|(_, camera, _)| camera.enabled)
+            .filter_map(|(entity, camera, render_camera)| {
+                let window = windows.get(render_camera?.window)?;
+                let position = window.cursor_position()?;
+                let size = match camera.input_area {
+                    InputArea::Rect { min, max } if camera.input_area.contains(position) => {
+                        let size = (max - min).abs();
+                        size.x * size.y
+                    }
+                    InputArea::Rect { .. } => return None,
+                    _ => window.width() * window.height(),
+                };
+                Some((entity, size))
+            })
+            .min_by_key(|(_, size)| FloatOrd(*size))
+            .map(|(entity, _)| entity);
+        if under.is_some() && active.entity != under {
+            active.entity = under;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lays_out_no_views() {
+        assert!(split_screen_grid(0, Vec2::new(800.0, 600.0)).is_empty());
+    }
+
+    #[test]
+    fn fills_the_window_with_one_view() {
+        let size = Vec2::new(800.0, 600.0);
+        assert_eq!(split_screen_grid(1, size), vec![(Vec2::ZERO, size)]);
+    }
+
+    #[test]
+    fn widens_the_last_row() {
+        let views = split_screen_grid(3, Vec2::new(800.0, 600.0));
+        assert_eq!(
+            views,
+            vec![
+                (Vec2::new(0.0, 300.0), Vec2::new(400.0, 600.0)),
+                (Vec2::new(400.0, 300.0), Vec2::new(800.0, 600.0)),
+                (Vec2::new(0.0, 0.0), Vec2::new(800.0, 300.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn covers_the_window_for_any_count() {
+        let size = Vec2::new(1280.0, 720.0);
+        for count in 1..=17 {
+            let views = split_screen_grid(count, size);
+            assert_eq!(views.len(), count);
+            let area: f32 = views
+                .iter()
+                .map(|(min, max)| (max.x - min.x) * (max.y - min.y))
+                .sum();
+            assert!((area - size.x * size.y).abs() < 1.0, "{} views", count);
+        }
+    }
+}
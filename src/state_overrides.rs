@@ -0,0 +1,209 @@
+//! Changing how cameras behave with the app's state.
+
+use crate::{CameraMode, OrbitCamera, OrbitCameraPlugin, OrbitCameraSystem};
+use bevy::ecs::component::Component;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::any::TypeId;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+/// Settings that replace those of an [`OrbitCamera`] while they apply. Fields
+/// left as `None` are untouched.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraOverrides {
+    pub mode: Option<CameraMode>,
+    pub enabled: Option<bool>,
+    pub rotation_locked: Option<bool>,
+    pub pitch_range: Option<RangeInclusive<f32>>,
+    pub yaw_range: Option<RangeInclusive<f32>>,
+    pub rotate_sensitivity: Option<f32>,
+    pub pan_sensitivity: Option<f32>,
+    pub zoom_sensitivity: Option<f32>,
+}
+
+impl CameraOverrides {
+    /// Applies the overrides, returning the values they replaced.
+    pub fn apply(&self, camera: &mut OrbitCamera) -> CameraOverrides {
+        fn swap<T: Clone>(field: &mut T, value: &Option<T>) -> Option<T> {
+            value
+                .as_ref()
+                .map(|value| std::mem::replace(field, value.clone()))
+        }
+        CameraOverrides {
+            mode: swap(&mut camera.mode, &self.mode),
+            enabled: swap(&mut camera.enabled, &self.enabled),
+            rotation_locked: swap(&mut camera.rotation_locked, &self.rotation_locked),
+            pitch_range: swap(&mut camera.pitch_range, &self.pitch_range),
+            yaw_range: swap(&mut camera.yaw_range, &self.yaw_range),
+            rotate_sensitivity: swap(&mut camera.rotate_sensitivity, &self.rotate_sensitivity),
+            pan_sensitivity: swap(&mut camera.pan_sensitivity, &self.pan_sensitivity),
+            zoom_sensitivity: swap(&mut camera.zoom_sensitivity, &self.zoom_sensitivity),
+        }
+    }
+
+    /// Each field of `self`, or else of `other`.
+    fn or(self, other: CameraOverrides) -> CameraOverrides {
+        CameraOverrides {
+            mode: self.mode.or(other.mode),
+            enabled: self.enabled.or(other.enabled),
+            rotation_locked: self.rotation_locked.or(other.rotation_locked),
+            pitch_range: self.pitch_range.or(other.pitch_range),
+            yaw_range: self.yaw_range.or(other.yaw_range),
+            rotate_sensitivity: self.rotate_sensitivity.or(other.rotate_sensitivity),
+            pan_sensitivity: self.pan_sensitivity.or(other.pan_sensitivity),
+            zoom_sensitivity: self.zoom_sensitivity.or(other.zoom_sensitivity),
+        }
+    }
+}
+
+/// What applied a layer of a camera's [`CameraOverrides`], from the lowest
+/// priority to the highest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum OverrideSource {
+    /// The [`StateOverrides`] of one state type.
+    State(TypeId),
+    /// The [`CameraZone`](crate::CameraZone) the camera is in.
+    Zone,
+}
+
+/// The [`CameraOverrides`] applied to one camera, stacked so that each can be
+/// lifted without undoing the others.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct OverrideStack {
+    layers: Vec<(OverrideSource, CameraOverrides)>,
+    /// The camera's own settings, from before any layer was applied.
+    replaced: Option<CameraOverrides>,
+}
+
+/// The [`CameraOverrides`] of [`StateOverrides`] and
+/// [`CameraZone`](crate::CameraZone)s applied to each camera. A zone's
+/// overrides apply over a state's, and lifting either leaves the other in
+/// place.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraOverrideStacks {
+    stacks: HashMap<Entity, OverrideStack>,
+}
+
+impl CameraOverrideStacks {
+    /// Replaces the layer `source` applies to `camera`, removing it if
+    /// `overrides` is `None`, and reapplies the whole stack in order.
+    pub(crate) fn set(
+        &mut self,
+        entity: Entity,
+        camera: &mut OrbitCamera,
+        source: OverrideSource,
+        overrides: Option<CameraOverrides>,
+    ) {
+        let stack = self.stacks.entry(entity).or_default();
+        if let Some(replaced) = stack.replaced.take() {
+            replaced.apply(camera);
+        }
+        stack.layers.retain(|(layer, _)| *layer != source);
+        if let Some(overrides) = overrides {
+            stack.layers.push((source, overrides));
+            stack.layers.sort_by_key(|(layer, _)| *layer);
+        }
+        // The first layer to set a field saw the camera's own value.
+        stack.replaced = stack
+            .layers
+            .iter()
+            .map(|(_, overrides)| overrides.apply(camera))
+            .reduce(CameraOverrides::or);
+        if stack.layers.is_empty() {
+            self.stacks.remove(&entity);
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    /// Forgets the overrides of despawned cameras.
+    pub fn override_stack_system(
+        mut stacks: ResMut<CameraOverrideStacks>,
+        cameras: Query<(), With<OrbitCamera>>,
+    ) {
+        if stacks
+            .stacks
+            .keys()
+            .any(|entity| cameras.get(*entity).is_err())
+        {
+            stacks
+                .stacks
+                .retain(|entity, _| cameras.get(*entity).is_ok());
+        }
+    }
+}
+
+/// A table of [`CameraOverrides`] for the camera on the same entity, applied
+/// while the app's `State<S>` is in the matching state and undone when it
+/// leaves. Needs a [`StateOverridesPlugin`] for `S`. A
+/// [`CameraZone`](crate::CameraZone) the camera is in applies over these.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateOverrides<S> {
+    pub table: Vec<(S, CameraOverrides)>,
+    applied: Option<S>,
+}
+
+impl<S> StateOverrides<S> {
+    pub fn new() -> Self {
+        StateOverrides {
+            table: Vec::new(),
+            applied: None,
+        }
+    }
+
+    pub fn with(mut self, state: S, overrides: CameraOverrides) -> Self {
+        self.table.push((state, overrides));
+        self
+    }
+}
+
+impl<S> Default for StateOverrides<S> {
+    fn default() -> Self {
+        StateOverrides::new()
+    }
+}
+
+/// Applies [`StateOverrides`] keyed by the app state `S`.
+pub struct StateOverridesPlugin<S>(PhantomData<S>);
+
+impl<S> Default for StateOverridesPlugin<S> {
+    fn default() -> Self {
+        StateOverridesPlugin(PhantomData)
+    }
+}
+
+impl<S: Component + Debug + Clone + Eq + Hash> StateOverridesPlugin<S> {
+    pub fn state_overrides_system(
+        state: Res<State<S>>,
+        mut stacks: ResMut<CameraOverrideStacks>,
+        mut query: Query<(Entity, &mut OrbitCamera, &mut StateOverrides<S>)>,
+    ) {
+        let current = state.current();
+        for (entity, mut camera, mut overrides) in query.iter_mut() {
+            if overrides.applied.as_ref() == Some(current) {
+                continue;
+            }
+            let row = overrides
+                .table
+                .iter()
+                .find(|(state, _)| state == current)
+                .map(|(_, row)| row.clone());
+            let source = OverrideSource::State(TypeId::of::<S>());
+            stacks.set(entity, &mut camera, source, row);
+            overrides.applied = Some(current.clone());
+        }
+    }
+}
+
+impl<S: Component + Debug + Clone + Eq + Hash> Plugin for StateOverridesPlugin<S> {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<CameraOverrideStacks>().add_system(
+            Self::state_overrides_system
+                .system()
+                .before(OrbitCameraSystem::Input),
+        );
+    }
+}
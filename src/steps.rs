@@ -0,0 +1,105 @@
+//! Rotating in exact increments from the keyboard.
+
+use crate::split_screen::receives_input;
+use crate::{
+    ActiveOrbitCamera, CameraFeedback, FeedbackKind, InputCapture, OrbitCamera, OrbitCameraPlugin,
+    OrbitTransition,
+};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// Turns the camera on the same entity by exactly `angle` radians per key
+/// press, animated over `duration` seconds, for reproducible views. Presses
+/// during an animation add to where it is heading.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardSteps {
+    pub left: Option<KeyCode>,
+    pub right: Option<KeyCode>,
+    pub up: Option<KeyCode>,
+    pub down: Option<KeyCode>,
+    pub angle: f32,
+    pub duration: f32,
+}
+
+impl Default for KeyboardSteps {
+    fn default() -> Self {
+        KeyboardSteps {
+            left: Some(KeyCode::Left),
+            right: Some(KeyCode::Right),
+            up: Some(KeyCode::Up),
+            down: Some(KeyCode::Down),
+            angle: 15f32.to_radians(),
+            duration: 0.2,
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn keyboard_step_system(
+        mut commands: Commands,
+        mut feedback: EventWriter<CameraFeedback>,
+        keyboard_input: Res<Input<KeyCode>>,
+        capture: Res<InputCapture>,
+        windows: Res<Windows>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        query: Query<(
+            Entity,
+            &OrbitCamera,
+            &KeyboardSteps,
+            Option<&OrbitTransition>,
+            Option<&Camera>,
+        )>,
+    ) {
+        if capture.keyboard {
+            return;
+        }
+        let pressed =
+            |key: Option<KeyCode>| key.map_or(false, |key| keyboard_input.just_pressed(key));
+        for (entity, camera, steps, transition, render_camera) in query.iter() {
+            let routed = receives_input(active.as_deref(), entity)
+                && camera.input_area.has_keyboard(&windows, render_camera);
+            if !camera.enabled || camera.rotation_locked || !routed {
+                continue;
+            }
+            let mut yaw = 0.0;
+            let mut pitch = 0.0;
+            if pressed(steps.left) {
+                yaw -= steps.angle;
+            }
+            if pressed(steps.right) {
+                yaw += steps.angle;
+            }
+            if pressed(steps.up) {
+                pitch -= steps.angle;
+            }
+            if pressed(steps.down) {
+                pitch += steps.angle;
+            }
+            if yaw == 0.0 && pitch == 0.0 {
+                continue;
+            }
+
+            // Keep heading wherever a running transition was going.
+            let mut step = OrbitTransition::new(steps.duration);
+            let (mut x, mut y) = (camera.x, camera.y);
+            if let Some(transition) = transition {
+                x = transition.x.unwrap_or(x);
+                y = transition.y.unwrap_or(y);
+                step.distance = transition.distance;
+                step.center = transition.center;
+            }
+            step.x = Some(x + yaw);
+            step.y = Some(
+                (y + pitch)
+                    .max(*camera.pitch_range.start())
+                    .min(*camera.pitch_range.end()),
+            );
+            commands.entity(entity).insert(step);
+            feedback.send(CameraFeedback {
+                camera: entity,
+                kind: FeedbackKind::SnapEngaged,
+            });
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Holding cameras still while their window can't be seen.
+
+use crate::{OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SuspendSettings {
+    /// Suspend cameras whose window has been minimized to nothing.
+    pub when_minimized: bool,
+    /// Suspend cameras whose window has lost focus.
+    pub when_unfocused: bool,
+}
+
+impl Default for SuspendSettings {
+    fn default() -> Self {
+        SuspendSettings {
+            when_minimized: true,
+            when_unfocused: false,
+        }
+    }
+}
+
+/// Present on cameras whose window is hidden. Smoothing, transitions, paths
+/// and other animations wait while it's there, so restoring the window
+/// doesn't reveal a camera that moved on without the user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CameraSuspended;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CameraSuspendEvent {
+    Suspended(Entity),
+    Resumed(Entity),
+}
+
+impl OrbitCameraPlugin {
+    pub fn suspend_system(
+        mut commands: Commands,
+        mut events: EventWriter<CameraSuspendEvent>,
+        settings: Res<SuspendSettings>,
+        windows: Res<Windows>,
+        query: Query<(Entity, &Camera, Option<&CameraSuspended>), With<OrbitCamera>>,
+    ) {
+        for (entity, camera, suspended) in query.iter() {
+            let hidden = windows.get(camera.window).map_or(false, |window| {
+                (settings.when_minimized && (window.width() <= 0.0 || window.height() <= 0.0))
+                    || (settings.when_unfocused && !window.is_focused())
+            });
+            // Resuming takes effect next frame, skipping the long frame that
+            // often follows a restore.
+            match (hidden, suspended.is_some()) {
+                (true, false) => {
+                    commands.entity(entity).insert(CameraSuspended);
+                    events.send(CameraSuspendEvent::Suspended(entity));
+                }
+                (false, true) => {
+                    commands.entity(entity).remove::<CameraSuspended>();
+                    events.send(CameraSuspendEvent::Resumed(entity));
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Orbiting slightly as the device is tilted.
+
+use crate::{OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+
+/// The latest device orientation as front-to-back and left-to-right tilt, in
+/// radians. With the `device_orientation` feature, web builds fill it in from
+/// the browser; elsewhere apps can write readings from their own sensors.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DeviceOrientation {
+    pub tilt: Option<Vec2>,
+}
+
+/// Turns the camera on the same entity a little with the tilt of the device,
+/// for a parallax effect on top of the usual controls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiltParallax {
+    /// Camera rotation per radian of tilt.
+    pub intensity: f32,
+    /// Largest offset, in radians, from where the controls put the camera.
+    pub max_angle: f32,
+    /// The tilt at which the camera is left where it is. Taken from the
+    /// first reading unless set.
+    pub neutral: Option<Vec2>,
+    applied: Vec2,
+}
+
+impl TiltParallax {
+    pub fn new(intensity: f32) -> Self {
+        TiltParallax {
+            intensity,
+            max_angle: 0.2,
+            neutral: None,
+            applied: Vec2::ZERO,
+        }
+    }
+
+    /// Makes the next reading the neutral orientation.
+    pub fn calibrate(&mut self) {
+        self.neutral = None;
+    }
+}
+
+impl Default for TiltParallax {
+    fn default() -> Self {
+        TiltParallax::new(0.3)
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn tilt_parallax_system(
+        orientation: Res<DeviceOrientation>,
+        mut query: Query<(&mut OrbitCamera, &mut TiltParallax)>,
+    ) {
+        let tilt = match orientation.tilt {
+            Some(tilt) => tilt,
+            None => return,
+        };
+        for (mut camera, mut parallax) in query.iter_mut() {
+            let neutral = *parallax.neutral.get_or_insert(tilt);
+            let offset = if camera.enabled {
+                let offset = (tilt - neutral) * parallax.intensity;
+                if offset.length() > parallax.max_angle {
+                    offset.normalize() * parallax.max_angle
+                } else {
+                    offset
+                }
+            } else {
+                Vec2::ZERO
+            };
+            // Only the change is applied, so dragging keeps working as usual.
+            let change = offset - parallax.applied;
+            if change != Vec2::ZERO {
+                parallax.applied = offset;
+                camera.x -= change.y;
+                camera.y = (camera.y - change.x)
+                    .max(*camera.pitch_range.start())
+                    .min(*camera.pitch_range.end());
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "device_orientation", target_arch = "wasm32"))]
+mod web {
+    use super::DeviceOrientation;
+    use crate::OrbitCameraPlugin;
+    use bevy::prelude::*;
+    use std::sync::{Arc, Mutex};
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+    use web_sys::DeviceOrientationEvent;
+
+    /// Readings pushed by the browser's `deviceorientation` listener.
+    #[derive(Clone, Default)]
+    pub struct BrowserOrientation(Arc<Mutex<Option<Vec2>>>);
+
+    impl OrbitCameraPlugin {
+        pub fn listen_device_orientation_system(latest: Res<BrowserOrientation>) {
+            let window = match web_sys::window() {
+                Some(window) => window,
+                None => return,
+            };
+            let latest = latest.0.clone();
+            let listener = Closure::wrap(Box::new(move |event: DeviceOrientationEvent| {
+                if let (Some(beta), Some(gamma)) = (event.beta(), event.gamma()) {
+                    if let Ok(mut latest) = latest.lock() {
+                        let tilt = Vec2::new(beta as f32, gamma as f32);
+                        *latest = Some(tilt * std::f32::consts::PI / 180.0);
+                    }
+                }
+            }) as Box<dyn FnMut(DeviceOrientationEvent)>);
+            if window
+                .add_event_listener_with_callback(
+                    "deviceorientation",
+                    listener.as_ref().unchecked_ref(),
+                )
+                .is_ok()
+            {
+                // The listener lives as long as the page.
+                listener.forget();
+            }
+        }
+
+        pub fn browser_orientation_system(
+            latest: Res<BrowserOrientation>,
+            mut orientation: ResMut<DeviceOrientation>,
+        ) {
+            if let Ok(latest) = latest.0.lock() {
+                if latest.is_some() {
+                    orientation.tilt = *latest;
+                }
+            }
+        }
+    }
+}
+#[cfg(all(feature = "device_orientation", target_arch = "wasm32"))]
+pub use web::BrowserOrientation;
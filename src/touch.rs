@@ -0,0 +1,438 @@
+//! Touch gestures.
+//!
+//! One finger orbits. Two fingers are decomposed every frame into a pan (the
+//! motion of their midpoint), a pinch (the change in their separation) and a
+//! twist (the change in the angle between them), all of which are applied at
+//! once so a single gesture can zoom, rotate and pan together.
+//!
+//! Pinch and twist only kick in once they pass the thresholds in
+//! [`TouchSettings`], so a two-finger pan doesn't drift in zoom or rotation.
+//! Short, still touches are reported as [`TouchGestureEvent`]s instead.
+//!
+//! Holding a finger still for [`TouchSettings::long_press_duration`] moves the
+//! orbit center to the point under it, as found by the
+//! [`Raycaster`](crate::Raycaster), and sends a [`FocusRipple`] so the app can
+//! draw feedback.
+
+#[cfg(feature = "touch")]
+use crate::ray::pick;
+#[cfg(feature = "touch")]
+use crate::split_screen::{receives_input, CameraInput};
+#[cfg(feature = "touch")]
+use crate::{
+    ActiveOrbitCamera, CameraEvents, CameraHistory, InputCapture, InputExclusions, InputRecorder,
+    OrbitCamera, OrbitCameraPlugin, OrbitPose, OrbitTransition, Raycaster, RoutedCameraEvents,
+    UiPointerCapture,
+};
+use bevy::prelude::*;
+#[cfg(feature = "touch")]
+use bevy::render::camera::Camera;
+use bevy::utils::HashMap;
+
+/// Thresholds for the touch gesture recognizer.
+///
+/// The defaults suit phones; large touchscreens usually want larger distances.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchSettings {
+    /// Change in finger separation, in pixels, before a pinch zooms.
+    pub pinch_activation_distance: f32,
+    /// Change in finger angle, in radians, before a twist rotates.
+    pub twist_activation_angle: f32,
+    /// Longest touch, in seconds, that still counts as a tap.
+    pub tap_max_duration: f32,
+    /// Furthest a finger may move, in pixels, and still count as a tap.
+    pub tap_max_movement: f32,
+    /// Longest gap, in seconds, between the taps of a double tap.
+    pub double_tap_interval: f32,
+    /// How long, in seconds, a finger must stay still to long-press.
+    pub long_press_duration: f32,
+    /// How long, in seconds, a long-press takes to move the orbit center.
+    pub long_press_recenter_duration: f32,
+}
+
+impl Default for TouchSettings {
+    fn default() -> Self {
+        TouchSettings {
+            pinch_activation_distance: 10.0,
+            twist_activation_angle: 0.1,
+            tap_max_duration: 0.25,
+            tap_max_movement: 10.0,
+            double_tap_interval: 0.3,
+            long_press_duration: 0.5,
+            long_press_recenter_duration: 0.4,
+        }
+    }
+}
+
+/// Discrete gestures recognized from touches, at their screen position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchGestureEvent {
+    Tap(Vec2),
+    DoubleTap(Vec2),
+    LongPress(Vec2),
+}
+
+/// Sent when a long-press moves the orbit center, so the app can show a
+/// ripple or similar indicator at the new center.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusRipple {
+    pub camera: Entity,
+    pub screen_position: Vec2,
+    pub world_position: Vec3,
+}
+
+/// The motion of the fingers on the screen during one frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TouchGesture {
+    /// One-finger drag, in pixels.
+    pub orbit: Vec2,
+    /// Two-finger midpoint drag, in pixels.
+    pub pan: Vec2,
+    /// Ratio of the finger separation to that of the previous frame.
+    pub pinch: f32,
+    /// Change in the angle between the fingers, in radians.
+    pub twist: f32,
+}
+
+impl TouchGesture {
+    pub fn one_finger(previous: Vec2, current: Vec2) -> Self {
+        TouchGesture {
+            orbit: current - previous,
+            pinch: 1.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn two_finger(previous: [Vec2; 2], current: [Vec2; 2]) -> Self {
+        let pan = (current[0] + current[1] - previous[0] - previous[1]) / 2.0;
+        let before = previous[1] - previous[0];
+        let after = current[1] - current[0];
+        let pinch = if before.length() > 0.0 && after.length() > 0.0 {
+            after.length() / before.length()
+        } else {
+            1.0
+        };
+        let twist = before.perp_dot(after).atan2(before.dot(after));
+        TouchGesture {
+            orbit: Vec2::ZERO,
+            pan,
+            pinch,
+            twist,
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.orbit == Vec2::ZERO && self.pan == Vec2::ZERO && self.pinch == 1.0 && self.twist == 0.0
+    }
+}
+
+struct Finger {
+    last: Vec2,
+    start: Vec2,
+    start_time: f64,
+    tappable: bool,
+    long_pressed: bool,
+}
+
+struct FingerPair {
+    ids: (u64, u64),
+    separation: f32,
+    angle: f32,
+    pinching: bool,
+    twisting: bool,
+}
+
+#[derive(Default)]
+pub struct TouchTracker {
+    fingers: HashMap<u64, Finger>,
+    pair: Option<FingerPair>,
+    last_tap: Option<(f64, Vec2)>,
+}
+
+impl TouchTracker {
+    /// Records the current touches and returns the gesture relative to the
+    /// previous frame. Fingers that just landed contribute nothing until the
+    /// next frame, so adding or lifting a finger never causes a jump.
+    pub fn update(
+        &mut self,
+        touches: &Touches,
+        settings: &TouchSettings,
+        now: f64,
+    ) -> TouchGesture {
+        let mut current: Vec<_> = touches.iter().map(|t| (t.id(), t.position())).collect();
+        current.sort_by_key(|(id, _)| *id);
+
+        let tracked: Vec<_> = current
+            .iter()
+            .filter_map(|(id, pos)| self.fingers.get(id).map(|finger| (*id, finger.last, *pos)))
+            .collect();
+        let gesture = match (current.len(), tracked.as_slice()) {
+            (1, [(_, last, pos)]) => TouchGesture::one_finger(*last, *pos),
+            (n, [(id0, last0, pos0), (id1, last1, pos1), ..]) if n >= 2 => {
+                let mut gesture = TouchGesture::two_finger([*last0, *last1], [*pos0, *pos1]);
+                self.gate_pair((*id0, *id1), [*pos0, *pos1], settings, &mut gesture);
+                gesture
+            }
+            _ => TouchGesture {
+                pinch: 1.0,
+                ..Default::default()
+            },
+        };
+        if current.len() < 2 {
+            self.pair = None;
+        }
+
+        let multi = current.len() >= 2;
+        for (id, pos) in current {
+            let finger = self.fingers.entry(id).or_insert(Finger {
+                last: pos,
+                start: pos,
+                start_time: now,
+                tappable: true,
+                long_pressed: false,
+            });
+            finger.last = pos;
+            if multi || finger.start.distance(pos) > settings.tap_max_movement {
+                finger.tappable = false;
+            }
+        }
+        gesture
+    }
+
+    /// Holds back pinch and twist until they pass their activation thresholds,
+    /// measured from when the two fingers first touched down together.
+    fn gate_pair(
+        &mut self,
+        ids: (u64, u64),
+        current: [Vec2; 2],
+        settings: &TouchSettings,
+        gesture: &mut TouchGesture,
+    ) {
+        let offset = current[1] - current[0];
+        let angle = offset.y.atan2(offset.x);
+        if self.pair.as_ref().map_or(true, |pair| pair.ids != ids) {
+            self.pair = Some(FingerPair {
+                ids,
+                separation: offset.length(),
+                angle,
+                pinching: false,
+                twisting: false,
+            });
+        }
+        let pair = self.pair.as_mut().unwrap();
+        pair.pinching |=
+            (offset.length() - pair.separation).abs() > settings.pinch_activation_distance;
+        let mut turned = (angle - pair.angle).abs();
+        if turned > std::f32::consts::PI {
+            turned = 2.0 * std::f32::consts::PI - turned;
+        }
+        pair.twisting |= turned > settings.twist_activation_angle;
+        if !pair.pinching {
+            gesture.pinch = 1.0;
+        }
+        if !pair.twisting {
+            gesture.twist = 0.0;
+        }
+    }
+
+    /// Reports a finger that has been held still long enough, once per touch.
+    pub fn long_presses(&mut self, settings: &TouchSettings, now: f64) -> Vec<TouchGestureEvent> {
+        let mut presses = Vec::new();
+        if self.fingers.len() != 1 {
+            return presses;
+        }
+        for finger in self.fingers.values_mut() {
+            if finger.tappable
+                && !finger.long_pressed
+                && now - finger.start_time >= settings.long_press_duration as f64
+            {
+                finger.long_pressed = true;
+                finger.tappable = false;
+                presses.push(TouchGestureEvent::LongPress(finger.last));
+            }
+        }
+        presses
+    }
+
+    /// Forgets lifted fingers, returning any taps they completed.
+    pub fn release(
+        &mut self,
+        touches: &Touches,
+        settings: &TouchSettings,
+        now: f64,
+    ) -> Vec<TouchGestureEvent> {
+        let mut taps = Vec::new();
+        for touch in touches.iter_just_released() {
+            let finger = match self.fingers.remove(&touch.id()) {
+                Some(finger) => finger,
+                None => continue,
+            };
+            let position = touch.position();
+            let is_tap = finger.tappable
+                && now - finger.start_time <= settings.tap_max_duration as f64
+                && finger.start.distance(position) <= settings.tap_max_movement;
+            if !is_tap {
+                continue;
+            }
+            match self.last_tap.take() {
+                Some((time, last))
+                    if now - time <= settings.double_tap_interval as f64
+                        && last.distance(position) <= settings.tap_max_movement =>
+                {
+                    taps.push(TouchGestureEvent::DoubleTap(position));
+                }
+                _ => {
+                    taps.push(TouchGestureEvent::Tap(position));
+                    self.last_tap = Some((now, position));
+                }
+            }
+        }
+        for touch in touches.iter_just_cancelled() {
+            self.fingers.remove(&touch.id());
+        }
+        taps
+    }
+}
+
+#[cfg(feature = "touch")]
+impl OrbitCameraPlugin {
+    #[allow(clippy::too_many_arguments)]
+    pub fn emit_touch_events(
+        mut events: EventWriter<CameraEvents>,
+        mut routed: EventWriter<RoutedCameraEvents>,
+        mut gestures: EventWriter<TouchGestureEvent>,
+        time: Res<Time>,
+        touches: Res<Touches>,
+        settings: Res<TouchSettings>,
+        mut tracker: Local<TouchTracker>,
+        windows: Res<Windows>,
+        recorder: Res<InputRecorder>,
+        capture: Res<InputCapture>,
+        ui: Res<UiPointerCapture>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        query: Query<(
+            Entity,
+            &OrbitCamera,
+            Option<&Camera>,
+            Option<&InputExclusions>,
+        )>,
+    ) {
+        let now = time.seconds_since_startup();
+        let gesture = tracker.update(&touches, &settings, now);
+        for press in tracker.long_presses(&settings, now) {
+            gestures.send(press);
+        }
+        for tap in tracker.release(&touches, &settings, now) {
+            gestures.send(tap);
+        }
+        if gesture.is_idle() || recorder.is_replaying() || capture.pointer_taken(&ui) {
+            return;
+        }
+        let mut input = CameraInput::default();
+        for (entity, camera, render_camera, exclusions) in query.iter() {
+            let sent = input.camera(entity, camera.input_area, active.is_some());
+            if !camera.enabled {
+                continue;
+            }
+            let excluded = exclusions.map_or(false, |exclusions| {
+                touches
+                    .iter()
+                    .any(|touch| exclusions.contains(touch.start_position()))
+            });
+            let area = camera.input_area;
+            let outside = touches
+                .iter()
+                .any(|touch| !area.contains(touch.start_position()));
+            if excluded || outside || !receives_input(active.as_deref(), entity) {
+                continue;
+            }
+            let width = render_camera
+                .and_then(|render_camera| windows.get(render_camera.window))
+                .map(|window| window.width());
+            // Touch positions grow upwards while mouse motion grows downwards.
+            // Twist and pinch follow the fingers one to one, so they are
+            // converted into the units the mouse events use.
+            let mut orbit = gesture.orbit * Vec2::new(1.0, -1.0) * camera.touch_rotate_sensitivity;
+            let rotation_per_pixel = camera.rotation_per_pixel(width);
+            if rotation_per_pixel != 0.0 {
+                orbit.x -= gesture.twist / rotation_per_pixel;
+            }
+            if orbit != Vec2::ZERO {
+                sent.push(CameraEvents::Orbit(orbit));
+            }
+            if gesture.pan != Vec2::ZERO {
+                let pan = gesture.pan * Vec2::new(1.0, -1.0) * camera.touch_pan_sensitivity;
+                sent.push(CameraEvents::Pan(pan));
+            }
+            let ln_sensitivity = camera.zoom_sensitivity.ln();
+            if gesture.pinch != 1.0 && ln_sensitivity != 0.0 {
+                let zoom = -gesture.pinch.ln() / ln_sensitivity * camera.touch_zoom_sensitivity;
+                sent.push(CameraEvents::Zoom(zoom));
+            }
+        }
+        input.send(&mut events, &mut routed);
+    }
+
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    pub fn long_press_focus_system(
+        mut commands: Commands,
+        mut gestures: EventReader<TouchGestureEvent>,
+        mut ripples: EventWriter<FocusRipple>,
+        windows: Res<Windows>,
+        raycaster: Res<Raycaster>,
+        settings: Res<TouchSettings>,
+        capture: Res<InputCapture>,
+        ui: Res<UiPointerCapture>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        mut query: Query<(
+            Entity,
+            &OrbitCamera,
+            &Camera,
+            &GlobalTransform,
+            Option<&mut CameraHistory>,
+            Option<&InputExclusions>,
+        )>,
+    ) {
+        for gesture in gestures.iter() {
+            let screen_position = match gesture {
+                TouchGestureEvent::LongPress(position) => *position,
+                _ => continue,
+            };
+            if capture.pointer_taken(&ui) {
+                continue;
+            }
+            for (entity, orbit, camera, transform, history, exclusions) in query.iter_mut() {
+                let excluded =
+                    exclusions.map_or(false, |exclusions| exclusions.contains(screen_position));
+                let routed = receives_input(active.as_deref(), entity)
+                    && orbit.input_area.contains(screen_position);
+                if !orbit.enabled || excluded || !routed {
+                    continue;
+                }
+                let hit = pick(
+                    &raycaster,
+                    entity,
+                    orbit,
+                    camera,
+                    transform,
+                    &windows,
+                    screen_position,
+                );
+                if let Some(world_position) = hit {
+                    if let Some(mut history) = history {
+                        history.push(OrbitPose::from(orbit));
+                    }
+                    commands.entity(entity).insert(OrbitTransition::to_center(
+                        world_position,
+                        settings.long_press_recenter_duration,
+                    ));
+                    ripples.send(FocusRipple {
+                        camera: entity,
+                        screen_position,
+                        world_position,
+                    });
+                }
+            }
+        }
+    }
+}
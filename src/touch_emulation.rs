@@ -0,0 +1,177 @@
+//! Emulating touches with the mouse, for trying touch controls on a desktop.
+
+use crate::OrbitCameraPlugin;
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::input::ElementState;
+use bevy::prelude::*;
+use bevy::window::WindowId;
+
+/// Finger ids for emulated touches, well clear of the ones platforms use.
+const FINGERS: [u64; 2] = [u64::MAX - 1, u64::MAX];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum EmulatedGesture {
+    OneFinger,
+    Pinch,
+    Pan,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Emulated {
+    gesture: EmulatedGesture,
+    window: WindowId,
+    anchor: Vec2,
+    fingers: [Vec2; 2],
+}
+
+/// Turns drags with `button` into touches while a modifier is held, so the
+/// touch controls can be tried without a touch screen. The button is hidden
+/// from the mouse controls during an emulated gesture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TouchEmulation {
+    pub enabled: bool,
+    pub button: MouseButton,
+    /// Held to drag with one finger.
+    pub one_finger_modifiers: Vec<KeyCode>,
+    /// Held to move two fingers mirrored about where the drag started, which
+    /// pinches when dragging towards or away from it and twists when
+    /// dragging around it.
+    pub pinch_modifiers: Vec<KeyCode>,
+    /// Held to drag two fingers side by side.
+    pub pan_modifiers: Vec<KeyCode>,
+    /// How far apart, in pixels, two emulated fingers start.
+    pub finger_spacing: f32,
+    active: Option<Emulated>,
+}
+
+impl TouchEmulation {
+    pub fn is_emulating(&self) -> bool {
+        self.active.is_some()
+    }
+
+    fn gesture(&self, keyboard_input: &Input<KeyCode>) -> Option<EmulatedGesture> {
+        let held = |keys: &[KeyCode]| keys.iter().any(|key| keyboard_input.pressed(*key));
+        if held(&self.pinch_modifiers) {
+            Some(EmulatedGesture::Pinch)
+        } else if held(&self.pan_modifiers) {
+            Some(EmulatedGesture::Pan)
+        } else if held(&self.one_finger_modifiers) {
+            Some(EmulatedGesture::OneFinger)
+        } else {
+            None
+        }
+    }
+
+    fn fingers(&self, gesture: EmulatedGesture, anchor: Vec2, cursor: Vec2) -> [Vec2; 2] {
+        let half = Vec2::new(self.finger_spacing / 2.0, 0.0);
+        match gesture {
+            EmulatedGesture::OneFinger => [cursor, cursor],
+            EmulatedGesture::Pinch => [cursor + half, anchor * 2.0 - cursor - half],
+            EmulatedGesture::Pan => [cursor - half, cursor + half],
+        }
+    }
+}
+
+impl Default for TouchEmulation {
+    fn default() -> Self {
+        TouchEmulation {
+            enabled: true,
+            button: MouseButton::Left,
+            one_finger_modifiers: vec![KeyCode::LAlt, KeyCode::RAlt],
+            pinch_modifiers: vec![KeyCode::LControl, KeyCode::RControl],
+            pan_modifiers: vec![KeyCode::LShift, KeyCode::RShift],
+            finger_spacing: 40.0,
+            active: None,
+        }
+    }
+}
+
+fn send_fingers(
+    touches: &mut EventWriter<TouchInput>,
+    gesture: EmulatedGesture,
+    fingers: [Vec2; 2],
+    phase: TouchPhase,
+) {
+    let count = match gesture {
+        EmulatedGesture::OneFinger => 1,
+        EmulatedGesture::Pinch | EmulatedGesture::Pan => 2,
+    };
+    for (id, position) in FINGERS.iter().zip(fingers.iter()).take(count) {
+        touches.send(TouchInput {
+            phase,
+            position: *position,
+            force: None,
+            id: *id,
+        });
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn touch_emulation_system(
+        mut emulation: ResMut<TouchEmulation>,
+        mut touches: EventWriter<TouchInput>,
+        mut button_events: EventReader<MouseButtonInput>,
+        mut mouse_button_input: ResMut<Input<MouseButton>>,
+        keyboard_input: Res<Input<KeyCode>>,
+        windows: Res<Windows>,
+    ) {
+        let button = emulation.button;
+        let released = button_events
+            .iter()
+            .any(|event| event.button == button && event.state == ElementState::Released);
+
+        if let Some(mut active) = emulation.active {
+            let cursor = windows
+                .get(active.window)
+                .and_then(|window| window.cursor_position());
+            match cursor {
+                Some(cursor) if !released && emulation.enabled => {
+                    active.fingers = emulation.fingers(active.gesture, active.anchor, cursor);
+                    send_fingers(
+                        &mut touches,
+                        active.gesture,
+                        active.fingers,
+                        TouchPhase::Moved,
+                    );
+                    emulation.active = Some(active);
+                }
+                _ => {
+                    send_fingers(
+                        &mut touches,
+                        active.gesture,
+                        active.fingers,
+                        TouchPhase::Ended,
+                    );
+                    emulation.active = None;
+                }
+            }
+            mouse_button_input.reset(button);
+            return;
+        }
+
+        if !emulation.enabled || !mouse_button_input.just_pressed(button) || released {
+            return;
+        }
+        let gesture = match emulation.gesture(&keyboard_input) {
+            Some(gesture) => gesture,
+            None => return,
+        };
+        let (window, cursor) = match windows
+            .iter()
+            .find_map(|window| window.cursor_position().map(|pos| (window.id(), pos)))
+        {
+            Some(cursor) => cursor,
+            None => return,
+        };
+        let fingers = emulation.fingers(gesture, cursor, cursor);
+        send_fingers(&mut touches, gesture, fingers, TouchPhase::Started);
+        emulation.active = Some(Emulated {
+            gesture,
+            window,
+            anchor: cursor,
+            fingers,
+        });
+        mouse_button_input.reset(button);
+    }
+}
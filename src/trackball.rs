@@ -0,0 +1,168 @@
+//! Free rotation without a fixed up direction.
+
+use crate::pivot::up_rotation;
+use crate::{CameraMode, CameraSuspended, OrbitCamera, OrbitCameraPlugin, OrbitCameraState};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::f32::consts::PI;
+
+/// How dragging turns an orbiting camera.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RotationMode {
+    /// Turns about `up`, keeping the horizon level, with the pitch limited by
+    /// `pitch_range` short of the poles.
+    #[default]
+    Turntable,
+    /// Gives the camera a [`Trackball`], for content with no natural up such
+    /// as molecules and point clouds.
+    Trackball,
+}
+
+/// Turns an orbiting camera on the same entity like a virtual trackball:
+/// dragging rotates about the camera's own axes, so it can roll over the
+/// poles. `x` and `y` follow the direction the camera looks from, and
+/// setting them points the camera along the new direction, ignoring
+/// `pitch_range` and `yaw_range`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trackball {
+    /// When set, removes accumulated roll at this many radians per second,
+    /// to keep the horizon level after tumbling.
+    pub auto_horizon: Option<f32>,
+    rotation: Option<Quat>,
+    synced: (f32, f32),
+}
+
+impl Trackball {
+    pub fn new() -> Self {
+        Trackball {
+            auto_horizon: None,
+            rotation: None,
+            synced: (f32::NAN, f32::NAN),
+        }
+    }
+
+    /// The orientation of the camera, once it has been used.
+    pub fn rotation(&self) -> Option<Quat> {
+        self.rotation
+    }
+
+    /// Turns by `delta` radians about the camera's up and right axes.
+    pub(crate) fn rotate(&mut self, camera: &mut OrbitCamera, delta: Vec2) {
+        let rotation = self.sync(camera);
+        let yaw = Quat::from_axis_angle(rotation * Vec3::Y, -delta.x);
+        let pitch = Quat::from_axis_angle(rotation * Vec3::X, -delta.y);
+        let rotation = (yaw * pitch * rotation).normalize();
+        self.rotation = Some(rotation);
+
+        // Keep the angles continuous so smoothing doesn't take the long way.
+        let back = up_rotation(camera.up).inverse() * (rotation * Vec3::Z);
+        let x = (-back.x).atan2(-back.z);
+        camera.x += wrap_angle(x - camera.x);
+        camera.y = back.y.clamp(-1.0, 1.0).acos();
+        self.synced = (camera.x, camera.y);
+    }
+
+    /// The current orientation, re-aimed if `x` or `y` were changed from
+    /// outside.
+    fn sync(&mut self, camera: &OrbitCamera) -> Quat {
+        let stale = self.synced != (camera.x, camera.y);
+        let rotation = match self.rotation {
+            Some(rotation) if !stale => rotation,
+            rotation => {
+                let up = match rotation {
+                    Some(rotation) => rotation * Vec3::Y,
+                    None => up_rotation(camera.up) * Vec3::Y,
+                };
+                let state = OrbitCameraState::from(camera);
+                let eye = state.transform(CameraMode::Orbit).translation;
+                let rotation = Transform::from_translation(eye)
+                    .looking_at(camera.center, up)
+                    .rotation;
+                if rotation.is_finite() {
+                    rotation
+                } else {
+                    state.transform(CameraMode::Orbit).rotation
+                }
+            }
+        };
+        self.rotation = Some(rotation);
+        self.synced = (camera.x, camera.y);
+        rotation
+    }
+
+    /// Where the camera is, given the smoothed `state`.
+    pub(crate) fn transform(&self, state: &OrbitCameraState) -> Option<Transform> {
+        let rotation = self.rotation?;
+        Some(Transform {
+            translation: state.center + rotation * Vec3::Z * state.distance,
+            rotation,
+            ..Default::default()
+        })
+    }
+}
+
+impl Default for Trackball {
+    fn default() -> Self {
+        Trackball::new()
+    }
+}
+
+pub(crate) fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+impl OrbitCameraPlugin {
+    /// Adds a [`Trackball`] to cameras switched to [`RotationMode::Trackball`],
+    /// and removes it again when they switch back.
+    pub fn rotation_mode_system(
+        mut commands: Commands,
+        mut modes: Local<HashMap<Entity, RotationMode>>,
+        query: Query<(Entity, &OrbitCamera, Option<&Trackball>), Changed<OrbitCamera>>,
+    ) {
+        for (entity, camera, trackball) in query.iter() {
+            let previous = modes.insert(entity, camera.rotation_mode);
+            match camera.rotation_mode {
+                RotationMode::Trackball if trackball.is_none() => {
+                    commands.entity(entity).insert(Trackball::new());
+                }
+                RotationMode::Turntable if previous == Some(RotationMode::Trackball) => {
+                    commands.entity(entity).remove::<Trackball>();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn trackball_system(
+        time: Res<Time>,
+        mut query: Query<(&OrbitCamera, &mut Trackball), Without<CameraSuspended>>,
+    ) {
+        for (camera, mut trackball) in query.iter_mut() {
+            if camera.mode != CameraMode::Orbit {
+                continue;
+            }
+            let rotation = trackball.sync(camera);
+            let speed = match trackball.auto_horizon {
+                Some(speed) if speed > 0.0 => speed,
+                _ => continue,
+            };
+            let back = rotation * Vec3::Z;
+            let up = rotation * Vec3::Y;
+            let world_up = up_rotation(camera.up) * Vec3::Y;
+            let level = world_up - back * world_up.dot(back);
+            if level.length_squared() < 1e-6 {
+                // Looking straight up or down, any roll is level.
+                continue;
+            }
+            let level = level.normalize();
+            let roll = back.dot(up.cross(level)).atan2(up.dot(level));
+            if roll.abs() < 1e-5 {
+                continue;
+            }
+            let max_step = speed * time.delta_seconds();
+            let step = roll.max(-max_step).min(max_step);
+            trackball.rotation = Some((Quat::from_axis_angle(back, step) * rotation).normalize());
+        }
+    }
+}
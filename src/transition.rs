@@ -0,0 +1,346 @@
+//! Timed moves of the orbit target.
+
+use crate::{CameraSuspended, OrbitCamera, OrbitCameraPlugin, OrbitPose, WorldOriginShifted};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::collections::VecDeque;
+
+/// How a transition speeds up and slows down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    Linear,
+    /// Eases in and out gently.
+    #[default]
+    SmoothStep,
+    /// Starts slowly, cubically.
+    EaseIn,
+    /// Stops slowly, cubically.
+    EaseOut,
+    /// Starts and stops slowly, cubically.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps progress `t` from zero to one onto how far along the move is.
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+        }
+    }
+}
+
+/// What happens to input while a transition runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransitionInput {
+    /// Mouse, touch, keyboard and gamepad input is ignored.
+    Locked,
+    /// Input keeps moving the camera, on top of the transition.
+    Blended,
+}
+
+/// What a transition does when it's inserted while another one is running.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Interruption {
+    /// Starts over from wherever the running transition got to, easing in
+    /// from a standstill.
+    #[default]
+    Replace,
+    /// Waits for the running transition, and any queued before it, to finish.
+    Queue,
+    /// Takes over at the speed the running transition was moving, so there's
+    /// no pause, and carries on towards its targets for any fields left as
+    /// `None`.
+    Blend,
+}
+
+/// How fast a transition was moving the target, per second.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct PoseRate {
+    angles: Vec2,
+    distance: f32,
+    center: Vec3,
+}
+
+impl PoseRate {
+    fn between(from: &OrbitPose, to: &OrbitPose, dt: f32) -> Self {
+        PoseRate {
+            angles: Vec2::new(to.x - from.x, to.y - from.y) / dt,
+            distance: (to.distance - from.distance) / dt,
+            center: (to.center - from.center) / dt,
+        }
+    }
+}
+
+/// Moves the target of the [`OrbitCamera`] on the same entity over
+/// `duration` seconds, then removes itself. Fields left as `None` keep
+/// their current value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrbitTransition {
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub distance: Option<f32>,
+    pub center: Option<Vec3>,
+    /// The `scale` of an orthographic camera, eased geometrically.
+    pub scale: Option<f32>,
+    pub duration: f32,
+    pub easing: Easing,
+    pub input: TransitionInput,
+    pub interruption: Interruption,
+    elapsed: f32,
+    start: Option<OrbitPose>,
+    start_scale: Option<f32>,
+    written: Option<OrbitPose>,
+    blended_angles: Vec2,
+    blended_zoom: f32,
+    blended_pan: Vec3,
+    velocity: PoseRate,
+    carried: PoseRate,
+}
+
+impl OrbitTransition {
+    pub fn new(duration: f32) -> Self {
+        OrbitTransition {
+            x: None,
+            y: None,
+            distance: None,
+            center: None,
+            scale: None,
+            duration,
+            easing: Easing::default(),
+            input: TransitionInput::Locked,
+            interruption: Interruption::default(),
+            elapsed: 0.0,
+            start: None,
+            start_scale: None,
+            written: None,
+            blended_angles: Vec2::ZERO,
+            blended_zoom: 1.0,
+            blended_pan: Vec3::ZERO,
+            velocity: PoseRate::default(),
+            carried: PoseRate::default(),
+        }
+    }
+
+    pub fn to_center(center: Vec3, duration: f32) -> Self {
+        OrbitTransition {
+            center: Some(center),
+            ..Self::new(duration)
+        }
+    }
+
+    pub fn to_pose(pose: OrbitPose, duration: f32) -> Self {
+        OrbitTransition {
+            x: Some(pose.x),
+            y: Some(pose.y),
+            distance: Some(pose.distance),
+            center: Some(pose.center),
+            ..Self::new(duration)
+        }
+    }
+
+    /// Takes over from `running` as set by its `interruption`, returning
+    /// what should run now.
+    fn interrupt(mut self, running: &OrbitTransition) -> Self {
+        if self.interruption == Interruption::Blend {
+            self.x = self.x.or(running.x);
+            self.y = self.y.or(running.y);
+            self.distance = self.distance.or(running.distance);
+            self.center = self.center.or(running.center);
+            self.scale = self.scale.or(running.scale);
+            self.carried = running.velocity;
+        }
+        self
+    }
+
+    /// Progress through the transition, from zero to one.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        }
+    }
+
+    pub fn locks_input(&self) -> bool {
+        self.input == TransitionInput::Locked
+    }
+
+    /// Moves the target and the poses it started from along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        if let Some(center) = &mut self.center {
+            *center += offset;
+        }
+        for pose in self.start.iter_mut().chain(self.written.iter_mut()) {
+            pose.center += offset;
+        }
+    }
+}
+
+/// The transitions each camera is running and has queued.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransitionTracker {
+    running: HashMap<Entity, OrbitTransition>,
+    queued: HashMap<Entity, VecDeque<OrbitTransition>>,
+}
+
+impl TransitionTracker {
+    fn shift_origin(&mut self, offset: Vec3) {
+        let queued = self.queued.values_mut().flat_map(|queue| queue.iter_mut());
+        for transition in self.running.values_mut().chain(queued) {
+            transition.shift_origin(offset);
+        }
+    }
+}
+
+impl OrbitCamera {
+    /// A transition to `center`, `distance` and `angles` as `x` and `y`, for
+    /// inserting on the camera's entity, such as to focus on an object.
+    pub fn animate_to(
+        center: Vec3,
+        distance: f32,
+        angles: Vec2,
+        duration: f32,
+        easing: Easing,
+    ) -> OrbitTransition {
+        OrbitTransition {
+            easing,
+            ..OrbitTransition::to_pose(
+                OrbitPose {
+                    x: angles.x,
+                    y: angles.y,
+                    distance,
+                    center,
+                },
+                duration,
+            )
+        }
+    }
+}
+
+impl OrbitCameraPlugin {
+    pub fn transition_system(
+        mut commands: Commands,
+        mut tracker: Local<TransitionTracker>,
+        time: Res<Time>,
+        mut origin_shifts: EventReader<WorldOriginShifted>,
+        mut query: Query<(
+            Entity,
+            &mut OrbitCamera,
+            &mut OrbitTransition,
+            Option<&CameraSuspended>,
+        )>,
+    ) {
+        for shift in origin_shifts.iter() {
+            tracker.shift_origin(shift.0);
+        }
+        // Forgets transitions that were removed.
+        let tracked: Vec<Entity> = tracker
+            .running
+            .keys()
+            .chain(tracker.queued.keys())
+            .copied()
+            .collect();
+        for entity in tracked {
+            if query.get_mut(entity).is_err() {
+                tracker.running.remove(&entity);
+                tracker.queued.remove(&entity);
+            }
+        }
+        for (entity, mut camera, mut transition, suspended) in query.iter_mut() {
+            if suspended.is_some() {
+                continue;
+            }
+            if transition.start.is_none() {
+                if let Some(running) = tracker.running.get(&entity) {
+                    if transition.interruption == Interruption::Queue {
+                        let queued = std::mem::replace(&mut *transition, running.clone());
+                        tracker.queued.entry(entity).or_default().push_back(queued);
+                    } else {
+                        *transition = transition.clone().interrupt(running);
+                        tracker.queued.remove(&entity);
+                    }
+                }
+            }
+            if transition.start.is_none() {
+                transition.start_scale = camera.scale;
+            }
+            let start = *transition
+                .start
+                .get_or_insert_with(|| OrbitPose::from(&*camera));
+            // Whatever moved the camera since the last frame was input.
+            if let (TransitionInput::Blended, Some(written)) =
+                (transition.input, transition.written)
+            {
+                transition.blended_angles += Vec2::new(camera.x - written.x, camera.y - written.y);
+                if written.distance > 0.0 {
+                    transition.blended_zoom *= camera.distance / written.distance;
+                }
+                transition.blended_pan += camera.center - written.center;
+            }
+            let dt = time.delta_seconds();
+            transition.elapsed += dt;
+            let t = transition.progress();
+            let eased = transition.easing.apply(t);
+            // Carries on at the interrupted transition's speed, fading out
+            // by the end.
+            let carry = transition.elapsed * (1.0 - t) * (1.0 - t);
+            let carried = transition.carried;
+
+            if let Some(x) = transition.x {
+                camera.x = start.x
+                    + (x - start.x) * eased
+                    + carried.angles.x * carry
+                    + transition.blended_angles.x;
+            }
+            if let Some(y) = transition.y {
+                camera.y = start.y
+                    + (y - start.y) * eased
+                    + carried.angles.y * carry
+                    + transition.blended_angles.y;
+            }
+            if let Some(distance) = transition.distance {
+                camera.distance = (start.distance
+                    + (distance - start.distance) * eased
+                    + carried.distance * carry)
+                    * transition.blended_zoom;
+            }
+            if let Some(center) = transition.center {
+                camera.center = start.center.lerp(center, eased)
+                    + carried.center * carry
+                    + transition.blended_pan;
+            }
+            if let Some(scale) = transition.scale {
+                camera.scale = Some(match transition.start_scale {
+                    Some(from) if from > 0.0 && scale > 0.0 => from * (scale / from).powf(eased),
+                    _ => scale,
+                });
+            }
+            let written = OrbitPose::from(&*camera);
+            if let (Some(previous), true) = (transition.written, dt > 0.0) {
+                transition.velocity = PoseRate::between(&previous, &written, dt);
+            }
+            transition.written = Some(written);
+            if t < 1.0 {
+                tracker.running.insert(entity, transition.clone());
+                continue;
+            }
+            tracker.running.remove(&entity);
+            let next = tracker
+                .queued
+                .get_mut(&entity)
+                .and_then(VecDeque::pop_front);
+            match next {
+                Some(next) => *transition = next,
+                None => {
+                    tracker.queued.remove(&entity);
+                    commands.entity(entity).remove::<OrbitTransition>();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Sharing viewpoints through the page URL in web builds.
+
+use crate::{OrbitCamera, OrbitCameraPlugin, OrbitPose};
+use bevy::prelude::*;
+use wasm_bindgen::JsValue;
+
+/// Keeps the pose of the [`OrbitCamera`] on the same entity in the page's URL
+/// hash, as a pose string, so the address can be shared. A pose already in
+/// the hash, or typed into it later, is applied to the camera. Only one
+/// camera per page should have this.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UrlHashPose {
+    /// How long, in seconds, the camera must rest before the URL is updated.
+    pub debounce: f32,
+    hash: Option<String>,
+    pending: Option<(OrbitPose, f64)>,
+}
+
+impl UrlHashPose {
+    pub fn new(debounce: f32) -> Self {
+        UrlHashPose {
+            debounce,
+            hash: None,
+            pending: None,
+        }
+    }
+}
+
+impl Default for UrlHashPose {
+    fn default() -> Self {
+        UrlHashPose::new(0.5)
+    }
+}
+
+fn read_hash() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    Some(hash.trim_start_matches('#').to_string())
+}
+
+fn write_hash(hash: &str) -> Option<()> {
+    // Replacing the current entry keeps navigation out of the back button.
+    web_sys::window()?
+        .history()
+        .ok()?
+        .replace_state_with_url(&JsValue::NULL, "", Some(&format!("#{}", hash)))
+        .ok()
+}
+
+impl OrbitCameraPlugin {
+    pub fn url_hash_system(
+        time: Res<Time>,
+        mut query: Query<(&mut OrbitCamera, &mut UrlHashPose)>,
+    ) {
+        let now = time.seconds_since_startup();
+        let hash = read_hash();
+        for (mut camera, mut sync) in query.iter_mut() {
+            if hash.is_some() && hash != sync.hash {
+                sync.hash = hash.clone();
+                sync.pending = None;
+                if let Some(pose) = hash
+                    .as_deref()
+                    .and_then(|hash| OrbitPose::from_pose_string(hash).ok())
+                {
+                    pose.apply(&mut camera);
+                    continue;
+                }
+            }
+
+            let pose = OrbitPose::from(&*camera);
+            let written = sync
+                .hash
+                .as_deref()
+                .and_then(|hash| OrbitPose::from_pose_string(hash).ok());
+            if written == Some(pose) {
+                sync.pending = None;
+                continue;
+            }
+            match sync.pending {
+                Some((pending, since)) if pending == pose => {
+                    if now - since >= sync.debounce as f64 {
+                        let string = pose.to_pose_string();
+                        if write_hash(&string).is_some() {
+                            sync.hash = Some(string);
+                        }
+                        sync.pending = None;
+                    }
+                }
+                _ => sync.pending = Some((pose, now)),
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Warnings about settings that silently stop the camera from working.
+
+use crate::{OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+use std::ops::RangeInclusive;
+
+fn inverted(range: &RangeInclusive<f32>) -> bool {
+    range.start() > range.end()
+}
+
+impl OrbitCamera {
+    /// Describes settings that are likely mistakes, such as inverted limits or
+    /// negative sensitivities.
+    pub fn misconfigurations(&self) -> Vec<&'static str> {
+        let mut problems = Vec::new();
+        if self.rotate_binding == self.pan_binding {
+            problems.push("rotate_binding and pan_binding are the same binding");
+        }
+        if Some(self.rotate_binding) == self.zoom_binding
+            || Some(self.pan_binding) == self.zoom_binding
+        {
+            problems.push("zoom_binding is the same as another binding");
+        }
+        if self.rotate_sensitivity < 0.0 || self.pan_sensitivity < 0.0 {
+            problems.push("rotate_sensitivity and pan_sensitivity should not be negative");
+        }
+        if self.zoom_sensitivity <= 0.0 {
+            problems.push("zoom_sensitivity should be greater than zero");
+        }
+        if self.coarse_zoom_multiplier < 0.0 {
+            problems.push("coarse_zoom_multiplier should not be negative");
+        }
+        if inverted(&self.pitch_range) {
+            problems.push("pitch_range starts after it ends");
+        }
+        if inverted(&self.yaw_range) {
+            problems.push("yaw_range starts after it ends");
+        }
+        if inverted(&self.distance_range) {
+            problems.push("distance_range starts after it ends");
+        }
+        if inverted(&self.fov_range) {
+            problems.push("fov_range starts after it ends");
+        }
+        if inverted(&self.scale_range) {
+            problems.push("scale_range starts after it ends");
+        }
+        if !self.up.normalize().is_finite() {
+            problems.push("up should be a non-zero, finite direction");
+        }
+        if self.distance <= 0.0 {
+            problems.push("distance should be greater than zero");
+        }
+        let smoothing = self.smoothing;
+        if smoothing.rotate < 0.0 || smoothing.pan < 0.0 || smoothing.zoom < 0.0 {
+            problems.push("smoothing time constants should not be negative");
+        }
+        problems
+    }
+}
+
+impl OrbitCameraPlugin {
+    /// Logs a warning for each misconfigured camera whenever its settings
+    /// change.
+    pub fn validate_system(
+        query: Query<(Entity, &OrbitCamera, Option<&Transform>), Changed<OrbitCamera>>,
+        mut warned: Local<Vec<(Entity, Vec<&'static str>)>>,
+    ) {
+        for (entity, camera, transform) in query.iter() {
+            let mut problems = camera.misconfigurations();
+            if transform.is_none() {
+                problems.push("the entity has no Transform to drive");
+            }
+            // Dragging changes the camera every frame, so only warn about new
+            // problems.
+            let known = warned.iter_mut().find(|(known, _)| *known == entity);
+            let fresh: Vec<_> = match &known {
+                Some((_, known)) => problems.iter().filter(|p| !known.contains(p)).collect(),
+                None => problems.iter().collect(),
+            };
+            for problem in fresh {
+                warn!("OrbitCamera on {:?}: {}", entity, problem);
+            }
+            match known {
+                Some((_, known)) => *known = problems,
+                None if !problems.is_empty() => warned.push((entity, problems)),
+                None => {}
+            }
+        }
+    }
+}
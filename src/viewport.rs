@@ -0,0 +1,83 @@
+//! Keeping the view clear of UI panels.
+
+use crate::{OrbitCameraPlugin, OrbitCameraState, ProjectionSwitch};
+use bevy::prelude::*;
+use bevy::render::camera::{
+    Camera, CameraProjection, OrthographicProjection, PerspectiveProjection,
+};
+
+/// The parts of the window, in logical pixels, covered by UI. The camera
+/// shifts its projection so the orbit center sits in the middle of what's
+/// left, and framing uses the visible area rather than the whole window.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ViewportMargins {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl ViewportMargins {
+    /// The visible rectangle as its bottom-left and top-right corners, in the
+    /// same space as `Window::cursor_position`.
+    pub fn visible_rect(&self, window_size: Vec2) -> (Vec2, Vec2) {
+        let min = Vec2::new(self.left, self.bottom);
+        let max = (window_size - Vec2::new(self.right, self.top)).max(min);
+        (min, max)
+    }
+
+    pub fn visible_size(&self, window_size: Vec2) -> Vec2 {
+        let (min, max) = self.visible_rect(window_size);
+        max - min
+    }
+
+    pub fn visible_center(&self, window_size: Vec2) -> Vec2 {
+        let (min, max) = self.visible_rect(window_size);
+        (min + max) / 2.0
+    }
+
+    pub fn contains(&self, window_size: Vec2, position: Vec2) -> bool {
+        let (min, max) = self.visible_rect(window_size);
+        position.cmpge(min).all() && position.cmple(max).all()
+    }
+
+    /// The clip-space shift that moves the middle of the window to the middle
+    /// of the visible rectangle.
+    pub fn ndc_offset(&self, window_size: Vec2) -> Vec2 {
+        if window_size.x <= 0.0 || window_size.y <= 0.0 {
+            return Vec2::ZERO;
+        }
+        (self.visible_center(window_size) / window_size) * 2.0 - Vec2::ONE
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::type_complexity)]
+    pub fn viewport_margins_system(
+        windows: Res<Windows>,
+        mut query: Query<(
+            &ViewportMargins,
+            &OrbitCameraState,
+            &mut Camera,
+            Option<&PerspectiveProjection>,
+            Option<&OrthographicProjection>,
+            Option<&ProjectionSwitch>,
+        )>,
+    ) {
+        for (margins, state, mut camera, perspective, orthographic, switch) in query.iter_mut() {
+            let window = match windows.get(camera.window) {
+                Some(window) => window,
+                None => continue,
+            };
+            let base = match (perspective, orthographic) {
+                (Some(perspective), _) => switch
+                    .and_then(|switch| switch.matrix(perspective, state.distance))
+                    .unwrap_or_else(|| perspective.get_projection_matrix()),
+                (None, Some(orthographic)) => orthographic.get_projection_matrix(),
+                (None, None) => continue,
+            };
+            let offset = margins.ndc_offset(Vec2::new(window.width(), window.height()));
+            camera.projection_matrix = Mat4::from_translation(offset.extend(0.0)) * base;
+        }
+    }
+}
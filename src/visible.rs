@@ -0,0 +1,71 @@
+//! Bringing points into view.
+
+use crate::{CameraMode, OrbitCamera, OrbitTransition};
+use bevy::prelude::*;
+use bevy::render::camera::PerspectiveProjection;
+
+impl OrbitCamera {
+    /// A transition that brings all of `points` into the view of an orbiting
+    /// camera over `duration` seconds without turning it, or `None` if they
+    /// are already visible. It zooms out, and moves the center as well only
+    /// when zooming out alone would more than double the distance.
+    pub fn ensure_visible(
+        &self,
+        points: &[Vec3],
+        projection: &PerspectiveProjection,
+        duration: f32,
+    ) -> Option<OrbitTransition> {
+        if self.mode != CameraMode::Orbit || points.is_empty() {
+            return None;
+        }
+        let rotation = self.compute_transform().rotation;
+        let right = rotation * Vec3::X;
+        let up = rotation * Vec3::Y;
+        let forward = rotation * -Vec3::Z;
+        let tan_y = (self.fov.unwrap_or(projection.fov) / 2.0).tan();
+        let tan_x = tan_y * projection.aspect_ratio;
+        if tan_x <= 0.0 || tan_y <= 0.0 {
+            return None;
+        }
+
+        // The distance from `center` at which every point is in the frustum.
+        let needed = |center: Vec3| {
+            points
+                .iter()
+                .map(|point| {
+                    let offset = *point - center;
+                    let depth = offset.dot(forward);
+                    let sideways = offset.dot(right).abs() / tan_x;
+                    let upwards = offset.dot(up).abs() / tan_y;
+                    sideways.max(upwards).max(projection.near) - depth
+                })
+                .fold(0.0, f32::max)
+        };
+
+        let distance = needed(self.center);
+        if distance <= self.distance {
+            return None;
+        }
+        let mut transition = OrbitTransition::new(duration);
+        if distance > self.distance * 2.0 {
+            // Center the points and the current center sideways, keeping depth.
+            let (mut min, mut max) = (Vec2::ZERO, Vec2::ZERO);
+            for point in points {
+                let offset = *point - self.center;
+                let flat = Vec2::new(offset.dot(right), offset.dot(up));
+                min = min.min(flat);
+                max = max.max(flat);
+            }
+            let middle = (min + max) / 2.0;
+            let center = self.center + right * middle.x + up * middle.y;
+            let shifted = needed(center);
+            if shifted < distance {
+                transition.center = Some(center);
+                transition.distance = Some(shifted.max(self.distance));
+                return Some(transition);
+            }
+        }
+        transition.distance = Some(distance);
+        Some(transition)
+    }
+}
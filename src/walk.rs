@@ -0,0 +1,99 @@
+//! Walking through a scene at eye level.
+
+use crate::pivot::up_rotation;
+use crate::{CameraFeedback, CameraMode, FeedbackKind, OrbitCamera, OrbitCameraPlugin};
+use bevy::prelude::*;
+
+/// Where a walking camera may stand. Given a position, returns the ground
+/// point beneath it, or `None` where walking isn't allowed.
+pub trait WalkSurface: Send + Sync + 'static {
+    fn ground(&self, position: Vec3) -> Option<Vec3>;
+}
+
+/// A horizontal floor at `height`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WalkPlane {
+    pub height: f32,
+}
+
+impl WalkSurface for WalkPlane {
+    fn ground(&self, position: Vec3) -> Option<Vec3> {
+        Some(Vec3::new(position.x, self.height, position.z))
+    }
+}
+
+impl<F: Fn(Vec3) -> Option<Vec3> + Send + Sync + 'static> WalkSurface for F {
+    fn ground(&self, position: Vec3) -> Option<Vec3> {
+        self(position)
+    }
+}
+
+/// Keeps a camera in [`CameraMode::Walk`] at `eye_height` above a
+/// [`WalkSurface`], such as a navmesh sampler. Moves onto places the surface
+/// rejects are undone.
+pub struct WalkConstraint {
+    pub eye_height: f32,
+    /// How far, in world units, one line of scrolling walks.
+    pub step: f32,
+    pub surface: Box<dyn WalkSurface>,
+    last_valid: Option<Vec3>,
+}
+
+impl WalkConstraint {
+    pub fn new(surface: impl WalkSurface) -> Self {
+        WalkConstraint {
+            eye_height: 1.7,
+            step: 0.5,
+            surface: Box::new(surface),
+            last_valid: None,
+        }
+    }
+
+    /// Moves the remembered positions along with the world.
+    pub(crate) fn shift_origin(&mut self, offset: Vec3) {
+        if let Some(last_valid) = &mut self.last_valid {
+            *last_valid += offset;
+        }
+    }
+}
+
+impl Default for WalkConstraint {
+    fn default() -> Self {
+        WalkConstraint::new(WalkPlane { height: 0.0 })
+    }
+}
+
+/// The direction a camera in [`CameraMode::Walk`] walks in, ignoring pitch.
+pub(crate) fn walk_forward(camera: &OrbitCamera) -> Vec3 {
+    up_rotation(camera.up) * Vec3::new(camera.x.sin(), 0.0, camera.x.cos())
+}
+
+impl OrbitCameraPlugin {
+    pub fn walk_constraint_system(
+        mut feedback: EventWriter<CameraFeedback>,
+        mut query: Query<(Entity, &mut OrbitCamera, &mut WalkConstraint)>,
+    ) {
+        for (entity, mut camera, mut walk) in query.iter_mut() {
+            if camera.mode != CameraMode::Walk {
+                walk.last_valid = None;
+                continue;
+            }
+            let standing = walk
+                .surface
+                .ground(camera.center)
+                .map(|ground| ground + up_rotation(camera.up) * Vec3::Y * walk.eye_height);
+            if let Some(center) = standing.or(walk.last_valid) {
+                if camera.center != center {
+                    camera.center = center;
+                    if standing.is_none() {
+                        feedback.send(CameraFeedback {
+                            camera: entity,
+                            kind: FeedbackKind::CollisionCorrected,
+                        });
+                    }
+                }
+                walk.last_valid = Some(center);
+            }
+        }
+    }
+}
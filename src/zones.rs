@@ -0,0 +1,165 @@
+//! Volumes that take over the camera while the followed target is inside.
+
+use crate::state_overrides::OverrideSource;
+use crate::trackball::wrap_angle;
+use crate::{
+    CameraOverrideStacks, CameraOverrides, FollowTarget, OrbitBounds, OrbitCamera,
+    OrbitCameraPlugin, OrbitTransition,
+};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::ops::RangeInclusive;
+
+/// A volume that changes how it's seen while the target of a
+/// [`FollowTarget`] is inside: the camera blends to a fixed yaw, a narrower
+/// pitch range or another distance, and back again once the target leaves,
+/// like the camera volumes of level editors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraZone {
+    /// The volume, in the local space of the zone's entity.
+    pub bounds: OrbitBounds,
+    /// Holds `x` here, in radians.
+    pub yaw: Option<f32>,
+    pub pitch_range: Option<RangeInclusive<f32>>,
+    pub distance: Option<f32>,
+    /// Seconds taken to blend to the zone's view.
+    pub blend_in: f32,
+    /// Seconds taken to blend back when the target leaves.
+    pub blend_out: f32,
+    /// Where zones overlap, the one with the highest priority applies.
+    pub priority: i32,
+}
+
+impl CameraZone {
+    pub fn new(bounds: OrbitBounds) -> Self {
+        CameraZone {
+            bounds,
+            yaw: None,
+            pitch_range: None,
+            distance: None,
+            blend_in: 0.5,
+            blend_out: 0.5,
+            priority: 0,
+        }
+    }
+
+    fn contains(&self, transform: &GlobalTransform, point: Vec3) -> bool {
+        let local = transform.compute_matrix().inverse().transform_point3(point);
+        local.cmpge(self.bounds.min).all() && local.cmple(self.bounds.max).all()
+    }
+}
+
+/// The zone a camera is in, and what it replaced.
+#[derive(Clone, Debug, PartialEq)]
+struct ActiveZone {
+    zone: Entity,
+    /// The distance from before the zones that changed it were entered.
+    distance: Option<f32>,
+    /// Seconds until the blend in is over and the limits take hold.
+    remaining: f32,
+    overrides: CameraOverrides,
+    applied: bool,
+}
+
+/// The zones each camera is in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ZoneTracker {
+    active: HashMap<Entity, ActiveZone>,
+}
+
+impl OrbitCameraPlugin {
+    pub fn camera_zone_system(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut tracker: Local<ZoneTracker>,
+        mut stacks: ResMut<CameraOverrideStacks>,
+        zones: Query<(Entity, &CameraZone, &GlobalTransform)>,
+        targets: Query<&GlobalTransform>,
+        mut cameras: Query<(Entity, &mut OrbitCamera, &FollowTarget)>,
+    ) {
+        let existing: Vec<Entity> = tracker.active.keys().copied().collect();
+        for entity in existing {
+            if cameras.get_mut(entity).is_err() {
+                tracker.active.remove(&entity);
+            }
+        }
+        for (entity, mut camera, follow) in cameras.iter_mut() {
+            let position = match targets.get(follow.target) {
+                Ok(transform) => transform.translation,
+                Err(_) => continue,
+            };
+            let inside = zones
+                .iter()
+                .filter(|(_, zone, transform)| zone.contains(transform, position))
+                .max_by_key(|(_, zone, _)| zone.priority);
+            let current = tracker.active.get(&entity).map(|active| active.zone);
+            if current.is_some() && current == inside.map(|(zone, _, _)| zone) {
+                let active = tracker.active.get_mut(&entity).unwrap();
+                active.remaining -= time.delta_seconds();
+                if active.remaining <= 0.0 && !active.applied {
+                    let overrides = Some(active.overrides.clone());
+                    stacks.set(entity, &mut camera, OverrideSource::Zone, overrides);
+                    active.applied = true;
+                }
+                continue;
+            }
+
+            let left = tracker.active.remove(&entity);
+            let prior = left.as_ref().and_then(|left| left.distance);
+            if left.as_ref().map_or(false, |left| left.applied) {
+                stacks.set(entity, &mut camera, OverrideSource::Zone, None);
+            }
+            let (zone_entity, zone) = match inside {
+                Some((zone_entity, zone, _)) => (zone_entity, zone),
+                None => {
+                    let prior = match prior {
+                        Some(prior) => prior,
+                        None => continue,
+                    };
+                    let blend_out = left
+                        .and_then(|left| zones.get(left.zone).ok())
+                        .map_or(0.0, |(_, zone, _)| zone.blend_out);
+                    let mut transition = OrbitTransition::new(blend_out);
+                    transition.distance = Some(prior);
+                    commands.entity(entity).insert(transition);
+                    continue;
+                }
+            };
+
+            let mut transition = OrbitTransition::new(zone.blend_in);
+            let mut overrides = CameraOverrides {
+                pitch_range: zone.pitch_range.clone(),
+                ..Default::default()
+            };
+            if let Some(yaw) = zone.yaw {
+                let x = camera.x + wrap_angle(yaw - camera.x);
+                transition.x = Some(x);
+                overrides.yaw_range = Some(x..=x);
+            }
+            if let Some(range) = &zone.pitch_range {
+                transition.y = Some(camera.y.max(*range.start()).min(*range.end()));
+            }
+            let distance = match zone.distance {
+                Some(distance) => {
+                    transition.distance = Some(distance);
+                    prior.or(Some(camera.distance))
+                }
+                None => {
+                    transition.distance = prior;
+                    None
+                }
+            };
+            commands.entity(entity).insert(transition);
+            tracker.active.insert(
+                entity,
+                ActiveZone {
+                    zone: zone_entity,
+                    distance,
+                    remaining: zone.blend_in,
+                    overrides,
+                    applied: false,
+                },
+            );
+        }
+    }
+}
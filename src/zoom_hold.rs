@@ -0,0 +1,131 @@
+//! Zooming for as long as a key or button is held.
+
+use crate::split_screen::{receives_input, CameraInput};
+use crate::{
+    ActiveOrbitCamera, CameraEvents, InputCapture, InputRecorder, OrbitCamera, OrbitCameraPlugin,
+    RoutedCameraEvents, Smoothing, UiPointerCapture,
+};
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+
+/// Zooms the camera on the same entity while any of the bound keys or mouse
+/// buttons is held, speeding up the longer it's held and easing in and out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinuousZoom {
+    pub zoom_in_keys: Vec<KeyCode>,
+    pub zoom_out_keys: Vec<KeyCode>,
+    pub zoom_in_buttons: Vec<MouseButton>,
+    pub zoom_out_buttons: Vec<MouseButton>,
+    /// Wheel lines per second as soon as the key is pressed.
+    pub speed: f32,
+    /// Wheel lines per second gained for every second it's held.
+    pub acceleration: f32,
+    pub max_speed: f32,
+    /// Time constant, in seconds, for starting and stopping.
+    pub ease: f32,
+    velocity: f32,
+    held_for: f32,
+}
+
+impl ContinuousZoom {
+    pub fn new() -> Self {
+        ContinuousZoom {
+            zoom_in_keys: vec![KeyCode::Equals, KeyCode::NumpadAdd],
+            zoom_out_keys: vec![KeyCode::Minus, KeyCode::NumpadSubtract],
+            zoom_in_buttons: Vec::new(),
+            zoom_out_buttons: Vec::new(),
+            speed: 2.0,
+            acceleration: 4.0,
+            max_speed: 10.0,
+            ease: 0.1,
+            velocity: 0.0,
+            held_for: 0.0,
+        }
+    }
+
+    /// Wheel lines per second being zoomed, positive when zooming in.
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+}
+
+impl Default for ContinuousZoom {
+    fn default() -> Self {
+        ContinuousZoom::new()
+    }
+}
+
+impl OrbitCameraPlugin {
+    #[allow(clippy::too_many_arguments)]
+    pub fn emit_continuous_zoom_events(
+        mut events: EventWriter<CameraEvents>,
+        mut routed: EventWriter<RoutedCameraEvents>,
+        time: Res<Time>,
+        keyboard_input: Res<Input<KeyCode>>,
+        mouse_button_input: Res<Input<MouseButton>>,
+        recorder: Res<InputRecorder>,
+        capture: Res<InputCapture>,
+        ui: Res<UiPointerCapture>,
+        windows: Res<Windows>,
+        active: Option<Res<ActiveOrbitCamera>>,
+        mut query: Query<(Entity, &OrbitCamera, &mut ContinuousZoom, Option<&Camera>)>,
+    ) {
+        if recorder.is_replaying() {
+            return;
+        }
+        let dt = time.delta_seconds();
+        let mut input = CameraInput::default();
+        for (entity, camera, mut zoom, render_camera) in query.iter_mut() {
+            // Cameras without a continuous zoom aren't seen here, so every
+            // camera is sent its own events.
+            let sent = input.camera(entity, camera.input_area, true);
+            let area = camera.input_area;
+            let receives = receives_input(active.as_deref(), entity);
+            let keyboard =
+                receives && !capture.keyboard && area.has_keyboard(&windows, render_camera);
+            let pointer =
+                receives && !capture.pointer_taken(&ui) && area.has_cursor(&windows, render_camera);
+            let held = |keys: &[KeyCode], buttons: &[MouseButton]| {
+                keyboard && keys.iter().any(|key| keyboard_input.pressed(*key))
+                    || pointer
+                        && buttons
+                            .iter()
+                            .any(|button| mouse_button_input.pressed(*button))
+            };
+            let direction = match (
+                held(&zoom.zoom_in_keys, &zoom.zoom_in_buttons),
+                held(&zoom.zoom_out_keys, &zoom.zoom_out_buttons),
+            ) {
+                (true, false) => 1.0,
+                (false, true) => -1.0,
+                _ => 0.0,
+            };
+            if !camera.enabled || (direction == 0.0 && zoom.velocity == 0.0) {
+                if zoom.velocity != 0.0 || zoom.held_for != 0.0 {
+                    zoom.velocity = 0.0;
+                    zoom.held_for = 0.0;
+                }
+                continue;
+            }
+
+            let target = if direction != 0.0 {
+                zoom.held_for += dt;
+                let speed = zoom.speed + zoom.acceleration * zoom.held_for;
+                direction * speed.min(zoom.max_speed)
+            } else {
+                zoom.held_for = 0.0;
+                0.0
+            };
+            let eased = zoom.velocity + (target - zoom.velocity) * Smoothing::factor(zoom.ease, dt);
+            zoom.velocity = if target == 0.0 && eased.abs() < 1e-3 {
+                0.0
+            } else {
+                eased
+            };
+            if zoom.velocity != 0.0 {
+                sent.push(CameraEvents::Zoom(zoom.velocity * dt));
+            }
+        }
+        input.send(&mut events, &mut routed);
+    }
+}